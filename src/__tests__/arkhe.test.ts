@@ -1,6 +1,47 @@
+import { mkdtempSync, rmSync } from 'fs';
+import { tmpdir } from 'os';
+import { join } from 'path';
 import { describe, it, expect } from 'vitest';
 import { Hypergraph } from '../arkhe/hypergraph.js';
+import { HypergraphBuilder, buildFromStream } from '../arkhe/builder.js';
 import { bootstrap } from '../arkhe/bootstrap.js';
+import { composeModels, exponentialDecay, regenerateToward, stepCoherence } from '../arkhe/coherence.js';
+import { consensus, majorityAggregator, meanAggregator } from '../arkhe/consensus.js';
+import { disjointUnion, product } from '../arkhe/constructions.js';
+import { diff, isEmptyDiff } from '../arkhe/diff.js';
+import { HYPERGRAPH_FORMAT_VERSION, loadEnvelope, saveEnvelope } from '../arkhe/envelope.js';
+import { divergentNodes, graphDigest } from '../arkhe/digest.js';
+import { erdosRenyi, lattice, preferentialAttachment } from '../arkhe/generators.js';
+import { assertInvariants, checkInvariants } from '../arkhe/invariants.js';
+import { ArkheError } from '../arkhe/errors.js';
+import type { HypergraphEvent } from '../arkhe/events.js';
+import { toDot, toMermaid } from '../arkhe/export.js';
+import { EvolutionLog } from '../arkhe/evolution.js';
+import { NodeHistory } from '../arkhe/history.js';
+import { GraphJournal } from '../arkhe/journal.js';
+import { fromGraphML, fromJsonGraph } from '../arkhe/interchange.js';
+import { merge } from '../arkhe/merge.js';
+import { findMotifs, isIsomorphic } from '../arkhe/motifs.js';
+import { collectMetrics } from '../arkhe/metrics.js';
+import { NodeIndex } from '../arkhe/node-index.js';
+import {
+  NodeSeriesRecorder,
+  ObservableRecorder,
+  edgeDensity,
+  meanCoherence,
+  nodeCount,
+} from '../arkhe/observables.js';
+import { partition } from '../arkhe/partition.js';
+import { CheckpointStore, FileBackend, MemoryBackend } from '../arkhe/storage.js';
+import { CoherenceReaper } from '../arkhe/reaper.js';
+import {
+  connectedComponents,
+  fieldMetric,
+  isConnected,
+  nearestNeighbors,
+  nodesWithin,
+  reachableFrom,
+} from '../arkhe/topology.js';
 import { SiliconConstitution } from '../arkhe/constitution.js';
 import { OntologicalSymbiosis } from '../arkhe/symbiosis.js';
 
@@ -16,6 +57,55 @@ describe('Arkhe(n) Core', () => {
     expect(h.edges[0].weight).toBe(0.8);
   });
 
+  it('should build hypergraphs fluently and reject duplicate IDs', () => {
+    const h = new HypergraphBuilder()
+      .withType('place_cell')
+      .node('p1')
+      .node('p2', { type: 'grid_cell' })
+      .edge(['p1', 'p2'], 0.6)
+      .build();
+
+    expect(h.nodes.get('p1')?.data.type).toBe('place_cell');
+    expect(h.nodes.get('p2')?.data.type).toBe('grid_cell');
+    expect(h.edges[0].weight).toBe(0.6);
+
+    const builder = new HypergraphBuilder().node('x').node('x');
+    expect(() => builder.build()).toThrow('Duplicate node IDs: x');
+  });
+
+  it('should raise coded errors instead of overwriting or ignoring', () => {
+    const h = new Hypergraph();
+    h.addNode('node1');
+
+    expect(() => h.addNode('node1')).toThrow(ArkheError);
+    let caught: unknown;
+    try {
+      h.addEdge(new Set(['node1', 'ghost']));
+    } catch (err) {
+      caught = err;
+    }
+    expect(caught).toBeInstanceOf(ArkheError);
+    expect((caught as ArkheError).code).toBe('NODE_NOT_FOUND');
+    expect((caught as ArkheError).nodeId).toBe('ghost');
+    expect(h.edges).toHaveLength(0);
+  });
+
+  it('should answer incidence queries over hyperedges', () => {
+    const h = new Hypergraph();
+    h.addNode('a');
+    h.addNode('b');
+    h.addNode('c');
+    h.addNode('d');
+    h.addEdge(new Set(['a', 'b', 'c']), 0.7, 'triad');
+    h.addEdge(new Set(['c', 'd']), 0.4);
+
+    expect(h.edgesOf('c')).toHaveLength(2);
+    expect(h.degree('a')).toBe(1);
+    expect(Array.from(h.neighbors('c')).sort()).toEqual(['a', 'b', 'd']);
+    expect(h.edgesContaining(['a', 'c'])[0].label).toBe('triad');
+    expect(Hypergraph.fromJSON(h.toJSON()).edges[0].label).toBe('triad');
+  });
+
   it('should run bootstrap step and update coherence', () => {
     const h = new Hypergraph();
     h.addNode('node1');
@@ -28,6 +118,1194 @@ describe('Arkhe(n) Core', () => {
     expect(h.totalCoherence()).toBe(0.5);
   });
 
+  it('should propagate coherence across hyperedges until convergence', () => {
+    const h = new Hypergraph();
+    h.addNode('hot').coherence = 1.0;
+    h.addNode('mid').coherence = 0.5;
+    h.addNode('cold').coherence = 0.0;
+    h.addEdge(new Set(['hot', 'mid']), 1.0);
+    h.addEdge(new Set(['mid', 'cold']), 1.0);
+
+    const report = h.propagateCoherence({ tolerance: 1e-9, maxIterations: 500 });
+    expect(report.converged).toBe(true);
+    expect(h.nodes.get('hot')?.coherence).toBeCloseTo(0.5, 5);
+    expect(h.nodes.get('cold')?.coherence).toBeCloseTo(0.5, 5);
+    expect(report.deltas['hot']).toBeCloseTo(-0.5, 5);
+    expect(report.globalCoherence).toBeCloseTo(0.5, 5);
+  });
+
+  it('should publish mutation events to subscribers', () => {
+    const h = new Hypergraph();
+    const seen: string[] = [];
+    const unsubscribe = h.events.subscribe((e) => seen.push(e.type));
+
+    h.addNode('a');
+    h.addNode('b');
+    h.addEdge(new Set(['a', 'b']), 0.5);
+    h.updateNode('a', { type: 'sensor' });
+    h.bootstrapStep();
+    h.removeNode('b');
+    unsubscribe();
+    h.addNode('c');
+
+    expect(seen).toEqual([
+      'NodeAdded', 'NodeAdded', 'EdgeAdded', 'StateChanged',
+      'CoherenceUpdated', 'CoherenceUpdated', 'EdgeRemoved', 'NodeRemoved',
+    ]);
+    expect(h.edges).toHaveLength(0);
+  });
+
+  it('should export hyperedges as auxiliary DOT vertices', () => {
+    const h = new Hypergraph();
+    h.addNode('a', { type: 'sensor' });
+    h.addNode('b');
+    h.addNode('c');
+    const edge = h.addEdge(new Set(['a', 'b', 'c']), 0.9, 'triad');
+
+    const dot = toDot(h, { shapes: { sensor: 'box' } });
+    expect(dot.startsWith('graph "arkhe" {')).toBe(true);
+    expect(dot).toContain('"a" [label="a\\nsensor\\nC=1.00", shape="box"');
+    expect(dot).toContain(`"edge:${edge.id}" -- "c";`);
+    expect(dot).toContain('xlabel="triad w=0.90"');
+  });
+
+  it('should export Mermaid flowcharts grouped by type', () => {
+    const h = new Hypergraph();
+    h.addNode('a', { type: 'sensor' });
+    h.addNode('b', { type: 'sensor' });
+    h.addNode('c');
+    h.addDirectedEdge(new Set(['a']), new Set(['c']), 0.5);
+    h.addEdge(new Set(['a', 'b', 'c']), 0.9, 'triad');
+
+    const chart = toMermaid(h);
+    expect(chart.split('\n')[0]).toBe('flowchart LR');
+    expect(chart).toContain('subgraph g0["sensor"]');
+    expect(chart).toContain('n0["a<br/>C=1.00"]');
+    expect(chart).toContain('n0 -->|"w=0.50"| n2');
+    expect(chart).toContain('e1(("triad w=0.90"))');
+    expect(chart).toContain('e1 --- n1');
+  });
+
+  it('should import JSON Graph Format and GraphML documents', () => {
+    const jgf = fromJsonGraph({
+      graph: {
+        directed: true,
+        nodes: { a: { label: 'Alpha', metadata: { coherence: 0.4 } }, b: {}, c: {} },
+        edges: [{ source: 'a', target: 'b', metadata: { weight: 0.7 } }],
+        hyperedges: [{ nodes: ['a', 'b', 'c'], label: 'triad' }],
+      },
+    }, { mapNode: (id, attrs) => ({ type: 'imported', ...attrs }) });
+    expect(jgf.nodes.get('a')?.data).toEqual({ type: 'imported', label: 'Alpha' });
+    expect(jgf.nodes.get('a')?.coherence).toBe(0.4);
+    expect(jgf.edges[0].sources).toEqual(new Set(['a']));
+    expect(jgf.edges[0].weight).toBe(0.7);
+    expect(jgf.edges[1].label).toBe('triad');
+
+    const gml = fromGraphML(`<?xml version="1.0"?>
+      <graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+        <key id="d0" for="node" attr.name="type" attr.type="string"/>
+        <key id="d1" for="edge" attr.name="weight" attr.type="double"/>
+        <graph id="G" edgedefault="undirected">
+          <node id="x"><data key="d0">sensor &amp; relay</data></node>
+          <node id="y"/>
+          <node id="z"/>
+          <edge source="x" target="y"><data key="d1">0.25</data></edge>
+          <hyperedge><endpoint node="x" type="out"/><endpoint node="y"/><endpoint node="z"/></hyperedge>
+        </graph>
+      </graphml>`);
+    expect(gml.nodes.get('x')?.data.type).toBe('sensor & relay');
+    expect(gml.edges[0].weight).toBe(0.25);
+    expect(gml.edges[0].sources).toBeUndefined();
+    expect(gml.edges[1].sources).toEqual(new Set(['x']));
+    expect(() => fromGraphML('<graphml/>')).toThrow(ArkheError);
+
+    const skipped: string[][] = [];
+    const onSkippedEdge = (edge: { nodes: string[] }) => skipped.push(edge.nodes);
+    const loops = fromGraphML(`<graphml><graph edgedefault="directed">
+          <node id="a"/><node id="b"/>
+          <edge source="a" target="a"/><edge source="a" target="b"/>
+        </graph></graphml>`, { onSkippedEdge });
+    expect(loops.edges).toHaveLength(1);
+    const jgfLoops = fromJsonGraph({
+      graph: { nodes: { a: {}, b: {} }, edges: [{ source: 'a', target: 'a' }, { source: 'b', target: 'a' }] },
+    }, { onSkippedEdge });
+    expect(jgfLoops.edges).toHaveLength(1);
+    expect(skipped).toEqual([['a', 'a'], ['a', 'a']]);
+  });
+
+  it('should roll back to an earlier snapshot', () => {
+    const h = new Hypergraph();
+    h.addNode('a', { level: 1 });
+    h.addNode('b');
+    h.addEdge(new Set(['a', 'b']), 0.4);
+    const snap = h.snapshot();
+
+    h.nodes.get('a')!.data.level = 2;
+    h.bootstrapStep();
+    h.removeNode('b');
+    h.rollback(snap);
+
+    expect(h.nodes.size).toBe(2);
+    expect(h.edges).toHaveLength(1);
+    expect(h.nodes.get('a')?.data.level).toBe(1);
+    expect(h.nodes.get('a')?.coherence).toBe(1.0);
+    expect(() => h.rollback('missing')).toThrow('Snapshot missing does not exist');
+  });
+
+  it('should collect entropy and connectivity metrics', () => {
+    const h = new Hypergraph();
+    h.addNode('a', { type: 'quark' });
+    h.addNode('b', { type: 'antiquark' });
+    h.addNode('c');
+    h.addEdge(new Set(['a', 'b']), 0.8);
+
+    const report = collectMetrics(h);
+    expect(report.entropy).toBeCloseTo(Math.log2(3), 5);
+    expect(report.connectivity.isolatedNodes).toBe(1);
+    expect(report.connectivity.maxDegree).toBe(1);
+    expect(report.connectivity.meanEdgeSize).toBe(2);
+    expect(report.globalCoherence).toBe(1.0);
+  });
+
+  it('should only propagate coherence along directed hyperedges', () => {
+    const h = new Hypergraph();
+    h.addNode('driver').coherence = 1.0;
+    h.addNode('follower').coherence = 0.0;
+    h.addDirectedEdge(new Set(['driver']), new Set(['follower']), 2.0);
+
+    h.propagateCoherence({ maxIterations: 200 });
+    expect(h.nodes.get('driver')?.coherence).toBe(1.0);
+    expect(h.nodes.get('follower')?.coherence).toBeCloseTo(1.0, 3);
+
+    const restored = Hypergraph.fromJSON(h.toJSON());
+    expect(Array.from(restored.edges[0].sources ?? [])).toEqual(['driver']);
+    expect(collectMetrics(restored).connectivity.directedEdges).toBe(1);
+  });
+
+  it('should query neighborhoods under a state metric', () => {
+    const h = new Hypergraph();
+    for (const [id, x] of [['a', 0], ['b', 1], ['c', 3], ['d', 10]] as const) {
+      h.addNode(id, { x });
+    }
+    const metric = fieldMetric('x');
+
+    expect(nodesWithin(h, 'a', 3, metric).map((n) => n.id)).toEqual(['b', 'c']);
+    expect(nearestNeighbors(h, 'd', 1, metric).map((n) => n.id)).toEqual(['c']);
+    expect(() => nodesWithin(h, 'zz', 1, metric)).toThrow('Node zz does not exist');
+  });
+
+  it('should map graphs while preserving structure', () => {
+    const h = new Hypergraph();
+    h.addNode('a', { x: 1 });
+    h.addNode('b', { x: 2 });
+    h.addNode('c', { x: 3 });
+    h.addEdge(new Set(['a', 'b', 'c']), 0.5);
+    h.setCoherence('b', 0.25);
+
+    const doubled = h.map((n) => ({ x: n.data.x * 2 }));
+    expect(doubled.nodes.get('c')?.data.x).toBe(6);
+    expect(doubled.nodes.get('b')?.coherence).toBe(0.25);
+    expect(doubled.edges[0].id).toBe(h.edges[0].id);
+    expect(h.nodes.get('c')?.data.x).toBe(3);
+
+    const odd = h.filterMapNodes((n) => (n.data.x % 2 === 1 ? n.data : undefined));
+    expect(Array.from(odd.nodes.keys())).toEqual(['a', 'c']);
+    expect(Array.from(odd.edges[0].nodes)).toEqual(['a', 'c']);
+    expect(h.edges[0].nodes.size).toBe(3);
+
+    expect(h.mapCoherence(() => 0).totalCoherence()).toBe(0);
+  });
+
+  it('should build disjoint unions and products of hypergraphs', () => {
+    const line = new HypergraphBuilder().node('0').node('1').edge(['0', '1'], 0.5).build();
+    const pair = new HypergraphBuilder().node('x').node('y').edge(['x', 'y'], 1.0).build();
+    line.setCoherence('1', 0.5);
+
+    const union = disjointUnion(line, line);
+    expect(union.nodes.size).toBe(4);
+    expect(union.edges).toHaveLength(2);
+    expect(union.nodes.get('right:1')?.coherence).toBe(0.5);
+    expect(() => disjointUnion(line, line, { leftPrefix: '', rightPrefix: '' })).toThrow();
+
+    const square = product(line, pair);
+    expect(square.nodes.size).toBe(4);
+    expect(square.edges).toHaveLength(4);
+    expect(square.nodes.get('1×y')?.data.right).toEqual({});
+    expect(square.nodes.get('1×y')?.coherence).toBe(0.5);
+  });
+
+  it('should extract induced subgraphs', () => {
+    const h = new Hypergraph();
+    for (const id of ['a', 'b', 'c', 'd']) h.addNode(id);
+    h.addEdge(new Set(['a', 'b', 'c']), 0.9);
+    h.addEdge(new Set(['c', 'd']), 0.2);
+    h.setCoherence('d', 0.1);
+
+    const sub = h.subgraph(['a', 'c']);
+    expect(sub.nodes.size).toBe(2);
+    expect(sub.edges).toHaveLength(1);
+    expect(Array.from(sub.edges[0].nodes)).toEqual(['a', 'c']);
+
+    const coherent = h.subgraphWhere((n) => n.coherence > 0.5);
+    expect(coherent.nodes.has('d')).toBe(false);
+    expect(coherent.edges).toHaveLength(1);
+    expect(() => h.subgraph(['zz'])).toThrow('Node zz does not exist');
+  });
+
+  it('should merge graphs under a conflict policy', () => {
+    const ours = new HypergraphBuilder().node('shared', { team: 'ours' }).node('a').edge(['shared', 'a']).build();
+    const theirs = new HypergraphBuilder().node('shared', { team: 'theirs' }).node('b').edge(['shared', 'b']).build();
+    ours.setCoherence('shared', 0.2);
+
+    const highest = merge(ours.mapCoherence((n) => n.coherence), theirs, 'keep-highest-coherence');
+    expect(highest.conflicts).toEqual([{ nodeId: 'shared', resolution: 'took-other' }]);
+
+    const report = merge(ours, theirs, 'rename-with-suffix');
+    expect(report.conflicts[0].renamedTo).toBe('shared#2');
+    expect(ours.nodes.get('shared')?.data.team).toBe('ours');
+    expect(ours.nodes.get('shared#2')?.data.team).toBe('theirs');
+    expect(Array.from(ours.edges[1].nodes)).toEqual(['shared#2', 'b']);
+    expect(report.edgesAdded).toBe(1);
+    expect(ours.edges[1].id).toBe(theirs.edges[0].id);
+
+    const left = new Hypergraph();
+    const right = new Hypergraph();
+    for (const g of [left, right]) {
+      for (const id of ['p', 'q', 'r']) g.addNode(id);
+    }
+    left.addEdge(new Set(['p', 'q']), 1, undefined, 'e1');
+    left.addEdge(new Set(['q', 'r']), 1, undefined, 'e2');
+    right.addEdge(new Set(['p', 'r']), 1, undefined, 'e1');
+    right.addEdge(new Set(['q', 'r']), 1, undefined, 'e2');
+    left.entangle('p', 'q', 1);
+    right.entangle('q', 'r', 0.5);
+    right.setCoherence('p', 0.2);
+
+    const joined = merge(left, right, 'prefer-other');
+    expect(joined.edgesAdded).toBe(1);
+    expect(joined.edgeConflicts).toHaveLength(1);
+    expect(joined.edgeConflicts[0].edgeId).toBe('e1');
+    expect(left.edges.map((e) => Array.from(e.nodes).join())).toEqual(['p,q', 'q,r', 'p,r']);
+    expect(joined.entanglementsAdded).toBe(1);
+    expect(left.entanglementsOf('r')).toEqual([{ a: 'r', b: 'q', strength: 0.5 }]);
+    expect(left.nodes.get('p')?.coherence).toBe(0.2);
+    expect(left.nodes.get('q')?.coherence).toBe(1);
+  });
+
+  it('should keep secondary indices in step with mutations', () => {
+    const h = new Hypergraph();
+    const index = new NodeIndex(h);
+    h.addNode('p1', { type: 'Psi' });
+    h.addNode('p2', { type: 'Psi' });
+    h.addNode('f1', { type: 'Phi' });
+    h.setCoherence('p1', 0.2);
+    h.setCoherence('f1', 0.6);
+
+    expect(index.nodesOfType('Psi').map((n) => n.id).sort()).toEqual(['p1', 'p2']);
+    expect(index.topKByCoherence(2).map((n) => n.id)).toEqual(['p2', 'f1']);
+    expect(index.bottomKByCoherence(1)[0].id).toBe('p1');
+
+    const snap = h.snapshot();
+    h.updateNode('p2', { type: 'Phi' });
+    h.removeNode('p1');
+    expect(index.nodesOfType('Psi')).toHaveLength(0);
+    expect(index.nodesOfType('Phi')).toHaveLength(2);
+
+    h.rollback(snap);
+    expect(index.nodesOfType('Psi')).toHaveLength(2);
+    expect(index.bottomKByCoherence(1)[0].id).toBe('p1');
+  });
+
+  it('should keep a NodeIndex ordered through a propagation pass', () => {
+    const h = erdosRenyi(8, 0.3, { seed: 13, coherence: (r) => r() });
+    const index = new NodeIndex(h);
+    h.propagateCoherence({ maxIterations: 1 });
+    const values = index.topKByCoherence(8).map((n) => n.coherence);
+    expect(values).toEqual([...values].sort((a, b) => b - a));
+    expect(values).toHaveLength(8);
+  });
+
+  it('should keep a NodeIndex ordered by coherence across merge', () => {
+    const ours = new Hypergraph();
+    ours.setCoherence(ours.addNode('a').id, 0.5);
+    const index = new NodeIndex(ours);
+    const theirs = new Hypergraph();
+    theirs.setCoherence(theirs.addNode('b').id, 0.1);
+
+    merge(ours, theirs);
+    expect(index.bottomKByCoherence(1)[0].id).toBe('b');
+    expect(index.topKByCoherence(1)[0].id).toBe('a');
+  });
+
+  it('should iterate, drain and upsert through entry APIs', () => {
+    const h = new Hypergraph();
+    h.addNode('a', { hits: 0 });
+    h.addNode('b');
+    h.addNode('c');
+    h.addEdge(new Set(['a', 'b', 'c']), 0.5);
+
+    expect(Array.from(h.nodeIds())).toEqual(['a', 'b', 'c']);
+    expect(Array.from(h, (n) => n.id)).toEqual(['a', 'b', 'c']);
+
+    h.entry('a').andModify((d) => ({ hits: d.hits + 1 }));
+    h.entry('z').orInsert({ hits: 5 });
+    expect(h.nodes.get('a')?.data.hits).toBe(1);
+    expect(h.entry('z').orInsert({ hits: 9 }).data.hits).toBe(5);
+
+    const drained = h.drainWhere((n) => n.id === 'b' || n.id === 'z');
+    expect(drained.map((n) => n.id)).toEqual(['b', 'z']);
+    expect(Array.from(h.edges[0].nodes)).toEqual(['a', 'c']);
+  });
+
+  it('should select nodes with chained query filters', () => {
+    const h = new Hypergraph();
+    const index = new NodeIndex(h);
+    h.addNode('n1', { type: 'Psi' });
+    h.addNode('p1', { type: 'Psi' });
+    h.addNode('p2', { type: 'Psi' });
+    h.addNode('f1', { type: 'Phi' });
+    h.addEdge(new Set(['n1', 'p1', 'f1']));
+    h.addEdge(new Set(['n1', 'p2']));
+    h.setCoherence('p2', 0.4);
+
+    expect(h.query().ofType('Psi').coherenceGt(0.8).connectedTo('n1').ids()).toEqual(['p1']);
+    expect(h.query(index).ofType('Psi').count()).toBe(3);
+    expect(h.query().connectedTo('n1').connectedTo('p1').ids()).toEqual(['f1']);
+    expect(h.query().coherenceLt(0.5).first()?.id).toBe('p2');
+  });
+
+  it('should commit or discard transactional mutations as a unit', () => {
+    const h = new Hypergraph();
+    h.addNode('source');
+
+    const created = h.transaction((tx) => {
+      tx.removeNode('source');
+      return tx.addNode('child').id;
+    });
+    expect(created).toBe('child');
+    expect(Array.from(h.nodeIds())).toEqual(['child']);
+
+    expect(() => h.transaction((tx) => {
+      tx.removeNode('child');
+      tx.addEdge(new Set(['child', 'ghost']));
+    })).toThrow('Node child does not exist');
+    expect(h.nodes.has('child')).toBe(true);
+
+    const result = h.transaction((tx, abort) => {
+      tx.addNode('scratch');
+      abort();
+    });
+    expect(result).toBeUndefined();
+    expect(h.nodes.has('scratch')).toBe(false);
+
+    const child = h.nodes.get('child')!;
+    h.addNode('peer');
+    const edge = h.addEdge(new Set(['child', 'peer']));
+    h.transaction((tx, abort) => {
+      tx.updateNode('child', { level: 3 });
+      tx.removeNode('child');
+      abort();
+    });
+    expect(h.nodes.get('child')).toBe(child);
+    expect(child.data).toEqual({});
+    expect(h.edges[0]).toBe(edge);
+    expect(edge.nodes).toEqual(new Set(['child', 'peer']));
+  });
+
+  it('should call every registered lifecycle hook', () => {
+    const h = new Hypergraph();
+    const created: string[] = [];
+    const changes: Array<[string, unknown]> = [];
+    h.registerHooks({ onNodeCreated: (n) => created.push(n.id) });
+    const unregister = h.registerHooks({
+      onNodeDestroyed: (n) => created.push(`-${n.id}`),
+      onStateChanged: (n, previous) => changes.push([n.id, previous.level]),
+    });
+
+    h.addNode('a', { level: 1 });
+    h.updateNode('a', { level: 2 });
+    h.removeNode('a');
+    unregister();
+    h.addNode('b');
+
+    expect(created).toEqual(['a', '-a', 'b']);
+    expect(changes).toEqual([['a', 1]]);
+
+    const g = new Hypergraph();
+    g.addNode('a', { level: 1 });
+    const ids = new Set(g.nodeIds());
+    const levels: unknown[] = [];
+    g.registerHooks({
+      onNodeCreated: (n) => ids.add(n.id),
+      onNodeDestroyed: (n) => ids.delete(n.id),
+      onStateChanged: (n) => levels.push(n.data.level),
+    });
+    g.transaction((tx, abort) => {
+      tx.addNode('tmp');
+      tx.updateNode('a', { level: 2 });
+      tx.removeNode('a');
+      abort();
+    });
+    expect(Array.from(ids)).toEqual(['a']);
+    expect(Array.from(g.nodeIds())).toEqual(['a']);
+    expect(levels).toEqual([2]);
+  });
+
+  it('should correlate coherence across entangled nodes', () => {
+    const h = new Hypergraph();
+    h.addNode('q');
+    h.addNode('anti');
+    h.addNode('mirror');
+    h.entangle('q', 'anti', 0.5);
+    h.entangle('q', 'mirror', -1);
+
+    h.setCoherence('q', 0.6);
+    expect(h.nodes.get('anti')?.coherence).toBeCloseTo(0.8, 10);
+    expect(h.nodes.get('mirror')?.coherence).toBe(1.0);
+    expect(h.entanglementsOf('q')).toHaveLength(2);
+
+    const restored = Hypergraph.fromJSON(h.toJSON());
+    expect(restored.entanglements()).toContainEqual({ a: 'anti', b: 'q', strength: 0.5 });
+
+    h.removeNode('q');
+    expect(h.entanglements()).toHaveLength(0);
+  });
+
+  it('should apply a batch of mutations atomically with one event', () => {
+    const h = new Hypergraph();
+    h.addNode('a');
+    const seen: string[] = [];
+    const batches: Array<Extract<HypergraphEvent, { type: 'BatchApplied' }>> = [];
+    h.events.subscribe((e) => {
+      seen.push(e.type);
+      if (e.type === 'BatchApplied') batches.push(e);
+    });
+    const index = new NodeIndex(h);
+
+    h.applyBatch([
+      { op: 'addNode', id: 'b', data: { type: 'agent' } },
+      { op: 'addEdge', nodes: ['a', 'b'], weight: 0.5 },
+      { op: 'addEdge', nodes: ['a', 'b'], weight: 0.1 },
+      { op: 'adjustCoherence', id: 'a', delta: -0.25 },
+      { op: 'adjustCoherence', id: 'b', delta: -0.5 },
+    ]);
+    h.applyBatch([{ op: 'removeEdge', edgeId: h.edges[1].id }]);
+    expect(seen).toEqual(['BatchApplied', 'BatchApplied']);
+    const [first, second] = batches;
+    expect(first.addedEdges.map((e) => e.weight)).toEqual([0.5, 0.1]);
+    expect(first.coherence).toEqual([{ nodeId: 'a', previous: 1, coherence: 0.75 }]);
+    expect(second.removedEdges.map((e) => e.weight)).toEqual([0.1]);
+
+    expect(h.edges).toHaveLength(1);
+    expect(h.nodes.get('a')?.coherence).toBe(0.75);
+    expect(index.nodesOfType('agent').map((n) => n.id)).toEqual(['b']);
+
+    const edgeId = h.edges[0].id;
+    expect(() => h.applyBatch([
+      { op: 'removeEdge', edgeId },
+      { op: 'removeNode', id: 'ghost' },
+    ])).toThrow(ArkheError);
+    expect(h.edges).toHaveLength(1);
+    expect(seen).toEqual(['BatchApplied', 'BatchApplied', 'GraphRestored']);
+  });
+
+  it('should run lifecycle hooks for batches and undo failed ones in place', () => {
+    const h = new Hypergraph();
+    h.setCoherence(h.addNode('a').id, 0.2);
+    h.addNode('b');
+    const index = new NodeIndex(h);
+    const log: string[] = [];
+    h.registerHooks({
+      onNodeCreated: (n) => log.push(`+${n.id}`),
+      onNodeDestroyed: (n) => log.push(`-${n.id}`),
+      onStateChanged: (n, previous) => log.push(`~${n.id}:${previous.type ?? ''}>${n.data.type}`),
+    });
+
+    h.applyBatch([
+      { op: 'addNode', id: 'c' },
+      { op: 'setState', id: 'a', data: { type: 'x' } },
+      { op: 'removeNode', id: 'b' },
+      { op: 'addEdge', nodes: ['a', 'c'] },
+    ]);
+    expect(log).toEqual(['+c', '-b', '~a:>x']);
+
+    expect(() => h.applyBatch([
+      { op: 'adjustCoherence', id: 'a', delta: 0.7 },
+      { op: 'removeNode', id: 'c' },
+      { op: 'removeNode', id: 'ghost' },
+    ])).toThrow(ArkheError);
+    expect(log).toHaveLength(3);
+    expect(h.nodes.get('a')?.coherence).toBe(0.2);
+    expect(Array.from(h.edges[0].nodes).sort()).toEqual(['a', 'c']);
+    expect(index.bottomKByCoherence(1)[0]).toBe(h.nodes.get('a'));
+    expect(index.topKByCoherence(1)[0]).toBe(h.nodes.get('c'));
+  });
+
+  it('should diff structure, states and coherence against a golden copy', () => {
+    const golden = new Hypergraph();
+    golden.addNode('a', { level: 1 });
+    golden.addNode('b', { level: 2 });
+    golden.addNode('c');
+    golden.addEdge(new Set(['a', 'b']), 0.5);
+    golden.addEdge(new Set(['b', 'c']), 0.5, 'link');
+
+    const evolved = Hypergraph.fromJSON(golden.toJSON());
+    expect(isEmptyDiff(diff(golden, evolved))).toBe(true);
+
+    evolved.updateNode('a', { level: 1.05 });
+    evolved.updateNode('b', { level: 3 });
+    evolved.setCoherence('b', 0.8);
+    evolved.removeNode('c');
+    evolved.addNode('d');
+    evolved.addEdge(new Set(['a', 'd']));
+    evolved.edges[0].weight = 0.9;
+
+    const d = diff(golden, evolved, {
+      distance: (x, y) => Math.abs(Number(x.level ?? 0) - Number(y.level ?? 0)),
+      tolerance: 0.1,
+    });
+    expect(d.addedNodes).toEqual(['d']);
+    expect(d.removedNodes).toEqual(['c']);
+    expect(d.changedStates.map((c) => c.nodeId)).toEqual(['b']);
+    expect(d.coherenceDeltas.b).toBeCloseTo(-0.2, 10);
+    expect(d.removedEdges.map((e) => e.label)).toEqual(['link']);
+    expect(d.addedEdges).toHaveLength(1);
+    expect(d.reweightedEdges[0].after.weight).toBe(0.9);
+    expect(d.addedEntanglements).toEqual([]);
+  });
+
+  it('should report added, removed and restrengthened entanglements in diff', () => {
+    const before = new Hypergraph();
+    for (const id of ['a', 'b', 'c']) before.addNode(id);
+    before.entangle('a', 'b', 0.5);
+    before.entangle('b', 'c', 0.2);
+
+    const after = Hypergraph.fromJSON(before.toJSON());
+    expect(isEmptyDiff(diff(before, after))).toBe(true);
+    after.disentangle('b', 'c');
+    after.entangle('a', 'c', -0.3);
+    after.entangle('b', 'a', 0.9);
+
+    const d = diff(before, after);
+    expect(isEmptyDiff(d)).toBe(false);
+    expect(d.removedEntanglements).toEqual([{ a: 'b', b: 'c', strength: 0.2 }]);
+    expect(d.addedEntanglements).toEqual([{ a: 'a', b: 'c', strength: -0.3 }]);
+    expect(d.changedEntanglements).toEqual([{ a: 'a', b: 'b', before: 0.5, after: 0.9 }]);
+  });
+
+  it('should produce order-independent digests that pinpoint divergence', () => {
+    const a = new Hypergraph();
+    a.addNode('x', { config: { depth: 2, mode: 'fast' } });
+    a.addNode('y');
+    a.addEdge(new Set(['x', 'y']), 0.5);
+
+    const b = new Hypergraph();
+    b.addNode('y');
+    b.addNode('x', { config: { mode: 'fast', depth: 2 } });
+    b.addEdge(new Set(['y', 'x']), 0.5);
+    expect(graphDigest(b).root).toBe(graphDigest(a).root);
+
+    b.setCoherence('y', 0.5);
+    const da = graphDigest(a);
+    const db = graphDigest(b);
+    expect(db.root).not.toBe(da.root);
+    expect(db.edgeRoot).toBe(da.edgeRoot);
+    expect(divergentNodes(da, db)).toEqual(['y']);
+    expect(graphDigest(b, { includeCoherence: false }).root).toBe(graphDigest(a, { includeCoherence: false }).root);
+
+    const linked = Hypergraph.fromJSON(a.toJSON());
+    linked.entangle('y', 'x', 0.4);
+    const dl = graphDigest(linked);
+    expect(dl.nodeRoot).toBe(da.nodeRoot);
+    expect(dl.edgeRoot).toBe(da.edgeRoot);
+    expect(dl.root).not.toBe(da.root);
+    const relinked = Hypergraph.fromJSON(a.toJSON());
+    relinked.entangle('x', 'y', 0.4);
+    expect(graphDigest(relinked).root).toBe(dl.root);
+    relinked.entangle('x', 'y', 0.5);
+    expect(graphDigest(relinked).entanglementRoot).not.toBe(dl.entanglementRoot);
+  });
+
+  it('should generate seeded random hypergraphs deterministically', () => {
+    const options = { seed: 7, coherence: (r: () => number) => r() };
+    const a = erdosRenyi(12, 0.3, options);
+    const b = erdosRenyi(12, 0.3, options);
+    expect(graphDigest(a).root).toBe(graphDigest(b).root);
+    expect(graphDigest(erdosRenyi(12, 0.3, { ...options, seed: 8 })).root).not.toBe(graphDigest(a).root);
+
+    const triples = erdosRenyi(6, 1, { edgeSize: 3 });
+    expect(triples.edges).toHaveLength(20);
+
+    const pa = preferentialAttachment(30, 2, { seed: 1, edgeSize: 3 });
+    expect(pa.nodes.size).toBe(30);
+    expect(pa.edges).toHaveLength(1 + 27 * 2);
+    expect(pa.edges.every((e) => e.nodes.size === 3)).toBe(true);
+    expect(isConnected(pa)).toBe(true);
+
+    const stuck = preferentialAttachment(10, 2, { random: () => 0, edgeSize: 3 });
+    expect(stuck.edges).toHaveLength(1 + 7 * 2);
+    expect(stuck.edges.every((e) => e.nodes.size === 3)).toBe(true);
+    for (const edgeSize of [1, 2.5]) {
+      expect(() => erdosRenyi(4, 1, { edgeSize })).toThrow('edgeSize');
+      expect(() => preferentialAttachment(4, 1, { edgeSize })).toThrow(ArkheError);
+    }
+
+    const grid = lattice(3, 4);
+    expect(grid.edges).toHaveLength(3 * 3 + 2 * 4);
+    expect(grid.nodes.get('n5')?.data).toEqual({ row: 1, col: 1 });
+    expect(lattice(3, 4, { periodic: true }).edges).toHaveLength(24);
+  });
+
+  it('should hold structural invariants across random mutations', () => {
+    for (let seed = 0; seed < 20; seed++) {
+      const h = erdosRenyi(10, 0.4, { seed, coherence: (r) => r() });
+      for (const id of ['n1', 'n4', 'n7']) h.removeNode(id);
+      h.propagateCoherence({ maxIterations: 5 });
+      expect(checkInvariants(h)).toEqual([]);
+    }
+
+    const h = new Hypergraph();
+    h.addNode('a');
+    h.addNode('b');
+    h.addEdge(new Set(['a', 'b']));
+    h.nodes.get('a')!.coherence = 1.5;
+    h.edges[0].nodes.add('ghost');
+    expect(checkInvariants(h).map((v) => v.kind)).toEqual(['coherence-out-of-range', 'missing-endpoint']);
+    expect(() => assertInvariants(h)).toThrow(ArkheError);
+  });
+
+  it('should keep public mutations within the structural invariants', () => {
+    const h = new Hypergraph();
+    h.addNode('a');
+    h.addNode('b');
+    expect(() => h.addEdge(new Set())).toThrow(ArkheError);
+    expect(() => h.addEdge(new Set(['a']))).toThrow(ArkheError);
+
+    h.addEdge(new Set(['a', 'b']), 3);
+    h.setCoherence('a', -0.5);
+    h.applyBatch([{ op: 'adjustCoherence', id: 'b', delta: 2 }]);
+    expect(h.nodes.get('a')?.coherence).toBe(0);
+    expect(h.nodes.get('b')?.coherence).toBe(1);
+    h.bootstrapStep();
+    expect(checkInvariants(h)).toEqual([]);
+
+    expect(checkInvariants(h.mapCoherence(() => 2))).toEqual([]);
+    h.propagateCoherence({ maxIterations: 1, kernel: () => -1 });
+    expect(checkInvariants(h)).toEqual([]);
+    const loaded = Hypergraph.fromJSON({ nodes: { a: {} }, edges: [], coherence: { a: 7 } });
+    expect(loaded.nodes.get('a')?.coherence).toBe(1);
+    expect(checkInvariants(erdosRenyi(4, 0.5, { coherence: () => -3 }))).toEqual([]);
+    expect(checkInvariants(product(loaded, loaded, { combineCoherence: (l, r) => l + r }))).toEqual([]);
+  });
+
+  it('should reach coherence-weighted consensus', () => {
+    const h = new Hypergraph();
+    h.addNode('a', { position: [0, 0], vote: 'left', temp: 10 });
+    h.addNode('b', { position: [4, 2], vote: 'right', temp: 20 });
+    h.addNode('c', { position: [4, 2], vote: 'right', temp: 20 });
+    h.addNode('sink');
+    h.setCoherence('a', 0.8);
+    h.setCoherence('b', 0.1);
+    h.setCoherence('c', 0.1);
+
+    const mean = consensus(h, ['a', 'b', 'c'], meanAggregator(), { createAs: 'avg' });
+    expect(mean.temp).toBeCloseTo(12, 10);
+    const [x, y] = mean.position as number[];
+    expect(x).toBeCloseTo(0.8, 10);
+    expect(y).toBeCloseTo(0.4, 10);
+    expect(mean.vote).toBe('left');
+    expect(h.nodes.get('avg')?.coherence).toBeCloseTo(1 / 3, 10);
+
+    h.setCoherence('b', 0.5);
+    h.setCoherence('c', 0.5);
+    consensus(h, ['a', 'b', 'c'], majorityAggregator('vote'), { target: 'sink' });
+    expect(h.nodes.get('sink')?.data.vote).toBe('right');
+    expect(() => consensus(h, [], meanAggregator())).toThrow(ArkheError);
+  });
+
+  it('should partition into balanced parts with a small cut', () => {
+    // Two 4-cliques joined by one light bridge.
+    const h = new Hypergraph();
+    const left = ['a', 'b', 'c', 'd'];
+    const right = ['w', 'x', 'y', 'z'];
+    for (const id of [...left, ...right]) h.addNode(id);
+    for (const group of [left, right]) {
+      for (let i = 0; i < group.length; i++) {
+        for (let j = i + 1; j < group.length; j++) h.addEdge(new Set([group[i], group[j]]));
+      }
+    }
+    h.addEdge(new Set(['d', 'w']), 0.1);
+
+    const p = partition(h, 2);
+    expect(p.parts.map((part) => part.length)).toEqual([4, 4]);
+    expect(p.assignment.get('a')).toBe(p.assignment.get('d'));
+    expect(p.assignment.get('a')).not.toBe(p.assignment.get('z'));
+    expect(p.cutEdges).toHaveLength(1);
+    expect(p.cutWeight).toBe(0.1);
+    expect(p.boundary).toEqual(new Set(['d', 'w']));
+
+    const grid = partition(lattice(4, 4), 4);
+    expect(grid.parts.every((part) => part.length <= 5)).toBe(true);
+    expect(() => partition(h, 0)).toThrow(ArkheError);
+    let caught: unknown;
+    try {
+      partition(h, 1.5);
+    } catch (err) {
+      caught = err;
+    }
+    expect((caught as ArkheError).code).toBe('INVALID_ARGUMENT');
+  });
+
+  it('should reap nodes that stay below the coherence threshold', () => {
+    const h = new Hypergraph();
+    h.addNode('hub');
+    h.addNode('fading');
+    h.addNode('flicker');
+    h.addEdge(new Set(['hub', 'fading']));
+    h.setCoherence('fading', 0.1);
+    h.setCoherence('flicker', 0.1);
+
+    const archived: string[] = [];
+    const reapedEvents: string[][] = [];
+    h.events.subscribe((e) => {
+      if (e.type === 'Reaped') reapedEvents.push(e.nodes.map((n) => n.id));
+    });
+    const reaper = new CoherenceReaper(h, {
+      threshold: 0.2,
+      ticks: 2,
+      archive: (node, edges) => archived.push(`${node.id}:${edges.length}`),
+    });
+
+    expect(reaper.observe()).toEqual([]);
+    h.setCoherence('flicker', 0.9);
+    expect(reaper.observe().map((n) => n.id)).toEqual(['fading']);
+    expect(reaper.streak('flicker')).toBe(0);
+    expect(h.nodes.has('fading')).toBe(false);
+    expect(h.edges).toHaveLength(0);
+    expect(archived).toEqual(['fading:1']);
+    expect(reapedEvents).toEqual([['fading']]);
+  });
+
+  it('should record observables every K ticks and export CSV', () => {
+    const h = new Hypergraph();
+    h.addNode('a', { type: 'agent' });
+    h.addNode('b');
+    const recorder = new ObservableRecorder([meanCoherence, nodeCount('agent'), edgeDensity], 2);
+
+    for (let t = 0; t < 5; t++) {
+      if (t === 1) h.addEdge(new Set(['a', 'b']));
+      if (t === 3) h.setCoherence('b', 0.5);
+      recorder.tick(h);
+    }
+    expect(recorder.rows.map((r) => r.tick)).toEqual([0, 2, 4]);
+    expect(recorder.series('edge_density')).toEqual([[0, 0], [2, 1], [4, 1]]);
+    expect(recorder.toCSV()).toBe([
+      'tick,mean_coherence,node_count:agent,edge_density',
+      '0,1,1,0',
+      '2,1,1,1',
+      '4,0.75,1,1',
+    ].join('\n'));
+  });
+
+  it('should recompute coherence incrementally around touched nodes', () => {
+    const a = disjointUnion(
+      lattice(3, 3, { seed: 3, coherence: (r) => r() }),
+      lattice(3, 3, { seed: 4, coherence: (r) => r() }),
+    );
+    a.propagateCoherence({ tolerance: 1e-9, maxIterations: 500 });
+    expect(a.dirtyNodes().size).toBe(0);
+    const b = Hypergraph.fromJSON(a.toJSON());
+    b.propagateCoherence();
+
+    for (const h of [a, b]) {
+      h.setCoherence('left:n0', 0.0);
+    }
+    expect(a.dirtyNodes()).toEqual(new Set(['left:n0']));
+    const partial = a.propagateCoherence({ tolerance: 1e-9, maxIterations: 500, incremental: true });
+    const full = b.propagateCoherence({ tolerance: 1e-9, maxIterations: 500 });
+    expect(Object.keys(partial.deltas).every((id) => id.startsWith('left:'))).toBe(true);
+    expect(Object.keys(full.deltas)).toHaveLength(18);
+    for (const id of a.nodeIds()) {
+      expect(a.nodes.get(id)!.coherence).toBeCloseTo(b.nodes.get(id)!.coherence, 6);
+    }
+    expect(a.propagateCoherence({ incremental: true }).iterations).toBe(0);
+
+    const sub = a.subgraphWhere((n) => n.id.startsWith('right:'));
+    expect(sub.dirtyNodes().size).toBe(9);
+    const fresh = sub.propagateCoherence({ tolerance: 1e-9, maxIterations: 500, incremental: true });
+    expect(fresh.iterations).toBeGreaterThan(0);
+  });
+
+  it('should find motifs and check isomorphism', () => {
+    const h = new Hypergraph();
+    h.addNode('s', { type: 'sensor' });
+    h.addNode('p', { type: 'processor' });
+    h.addNode('q', { type: 'processor' });
+    h.addNode('a', { type: 'actuator' });
+    h.addEdge(new Set(['s', 'p']));
+    h.addEdge(new Set(['s', 'q']));
+    h.addEdge(new Set(['p', 'a']));
+    h.addEdge(new Set(['s', 'p', 'q']));
+
+    const chain = findMotifs(h, {
+      nodes: [
+        { name: 'in', where: (n) => n.data.type === 'sensor' },
+        { name: 'mid' },
+        { name: 'out', where: (n) => n.data.type === 'actuator' },
+      ],
+      edges: [['in', 'mid'], ['mid', 'out']],
+    });
+    expect(chain).toEqual([{ in: 's', mid: 'p', out: 'a' }]);
+
+    const triads = findMotifs(h, { nodes: [{ name: 'x' }, { name: 'y' }, { name: 'z' }], edges: [['x', 'y', 'z']] });
+    expect(triads).toHaveLength(6);
+    expect(findMotifs(h, { nodes: [{ name: 'x' }, { name: 'y' }], edges: [['x', 'y']] }, { limit: 2 })).toHaveLength(2);
+
+    const relabeled = new Hypergraph();
+    for (const id of ['1', '2', '3', '4']) relabeled.addNode(id);
+    relabeled.addEdge(new Set(['4', '2']));
+    relabeled.addEdge(new Set(['4', '3']));
+    relabeled.addEdge(new Set(['2', '1']));
+    relabeled.addEdge(new Set(['2', '3', '4']));
+    expect(isIsomorphic(h, relabeled)).toBe(true);
+    expect(isIsomorphic(h, relabeled, { nodeMatch: (x, y) => x.data.type === y.data.type })).toBe(false);
+    relabeled.edges[0].weight = 0.2;
+    relabeled.removeNode('1');
+    expect(isIsomorphic(h, relabeled)).toBe(false);
+  });
+
+  it('should export per-node time series as partitioned CSV', () => {
+    const h = new Hypergraph();
+    h.addNode('a', { pos: [1, 2], active: true, label: 'x' });
+    h.addNode('b,1', { energy: { level: 3 } });
+    const recorder = new NodeSeriesRecorder();
+    for (let t = 0; t < 3; t++) {
+      h.updateNode('a', { pos: [t, 2] });
+      recorder.tick(h);
+    }
+
+    expect(recorder.toCSV({ toTick: 1 })).toBe([
+      'tick,node,coherence,active,energy.level,pos.0,pos.1',
+      '0,a,1,1,,0,2',
+      '0,"b,1",1,,3,,',
+    ].join('\n'));
+    const parts = recorder.partitionedCSV(2);
+    expect(parts.map((p) => [p.fromTick, p.toTick])).toEqual([[0, 2], [2, 4]]);
+    expect(parts[1].csv.split('\n')).toHaveLength(3);
+  });
+
+  it('should step coherence through pluggable decay and regeneration models', () => {
+    const h = new Hypergraph();
+    h.addNode('a');
+    h.setCoherence(h.addNode('b').id, 0.2);
+    const updates: string[] = [];
+    h.events.subscribe((e) => {
+      if (e.type === 'CoherenceUpdated') updates.push(e.nodeId);
+    });
+
+    stepCoherence(h, exponentialDecay(0.5));
+    expect(h.nodes.get('a')?.coherence).toBe(0.5);
+    expect(h.nodes.get('b')?.coherence).toBeCloseTo(0.1, 10);
+    expect(updates).toEqual(['a', 'b']);
+
+    const model = composeModels(exponentialDecay(0.1), regenerateToward(0.6, 0.5));
+    for (let t = 0; t < 50; t++) stepCoherence(h, model);
+    // Fixed point of c -> 0.9c + (0.6 - 0.9c) / 2.
+    expect(h.nodes.get('a')?.coherence).toBeCloseTo(0.6 / 1.1, 6);
+    expect(h.nodes.get('b')?.coherence).toBeCloseTo(0.6 / 1.1, 6);
+
+    stepCoherence(h, { next: (node) => node.coherence + 2 });
+    expect(h.nodes.get('a')?.coherence).toBe(1);
+
+    for (const order of [['a', 'b'], ['b', 'a']]) {
+      const g = new Hypergraph();
+      for (const id of order) g.addNode(id);
+      g.setCoherence('b', 0.4);
+      g.entangle('a', 'b', -1);
+      stepCoherence(g, exponentialDecay(0.5));
+      expect(g.nodes.get('a')?.coherence).toBeCloseTo(0.7, 10);
+      expect(g.nodes.get('b')?.coherence).toBeCloseTo(0.7, 10);
+    }
+  });
+
+  it('should answer time-travel queries from per-node history', () => {
+    const h = new Hypergraph();
+    h.addNode('a', { level: 0 });
+    const history = new NodeHistory(h);
+    for (let t = 1; t <= 4; t++) {
+      history.tick();
+      h.updateNode('a', { level: t });
+      h.setCoherence('a', 1 - t / 10);
+    }
+    expect(history.stateAt('a', 0)).toEqual({ level: 0 });
+    expect(history.stateAt('a', 2)).toEqual({ level: 2 });
+    expect(history.coherenceAt('a', 3)).toBeCloseTo(0.7, 10);
+    expect(history.coherenceTrajectory('a').map(([t]) => t)).toEqual([0, 1, 2, 3, 4]);
+
+    history.tick();
+    h.applyBatch([{ op: 'adjustCoherence', id: 'a', delta: -0.5 }]);
+    expect(history.coherenceAt('a', 5)).toBeCloseTo(0.1, 10);
+    history.tick();
+    h.removeNode('a');
+    expect(history.stateAt('a', 6)).toBeUndefined();
+    expect(history.stateAt('a', 4)).toEqual({ level: 4 });
+    history.tick();
+    history.tick();
+    h.addNode('a', { level: 8 });
+    expect(history.stateAt('a', 7)).toBeUndefined();
+    expect(history.stateAt('a', 8)).toEqual({ level: 8 });
+    expect(history.stateAt('a', 5)).toEqual({ level: 4 });
+
+    const g = new Hypergraph();
+    g.addNode('b');
+    const bounded = new NodeHistory(g, { keepLast: 2, keepEvery: 2 });
+    for (let t = 1; t <= 5; t++) {
+      bounded.tick();
+      g.setCoherence('b', t / 10);
+    }
+    expect(bounded.coherenceTrajectory('b').map(([t]) => t)).toEqual([4, 5]);
+    expect(bounded.stateAt('b', 3)).toBeUndefined();
+  });
+
+  it('should save tick checkpoints as deltas and load any of them back', () => {
+    const h = erdosRenyi(8, 0.4, { seed: 5, coherence: (r) => r() });
+    const backend = new MemoryBackend();
+    const store = new CheckpointStore({ backend, fullEvery: 2 });
+    const saved: string[] = [];
+    for (let tick = 0; tick < 6; tick++) {
+      if (tick === 1) h.removeNode('n3');
+      if (tick === 2) h.entangle('n0', 'n1', 0.5);
+      if (tick === 3) h.addEdge(new Set([h.addNode('x', { born: 3 }).id, 'n0']), 0.2);
+      if (tick === 4) h.nodes.get('n1')!.data.mutated = true;
+      h.propagateCoherence();
+      store.saveCheckpoint(tick, h);
+      saved.push(JSON.stringify(h.toJSON()));
+    }
+    expect(store.ticks()).toEqual([0, 1, 2, 3, 4, 5]);
+    const kinds = store.ticks().map((t) => JSON.parse(backend.get(`checkpoint-${t}`)!).kind);
+    expect(kinds).toEqual(['full', 'delta', 'delta', 'full', 'delta', 'delta']);
+    for (let tick = 0; tick < 6; tick++) {
+      expect(JSON.stringify(store.loadCheckpoint(tick).toJSON())).toBe(saved[tick]);
+    }
+    expect(() => store.saveCheckpoint(5, h)).toThrow(ArkheError);
+    expect(() => store.loadCheckpoint(9)).toThrow('Checkpoint 9 does not exist');
+
+    const dir = mkdtempSync(join(tmpdir(), 'arkhe-'));
+    try {
+      new CheckpointStore({ backend: new FileBackend(dir) }).saveCheckpoint(7, h);
+      const reopened = new CheckpointStore({ backend: new FileBackend(dir) });
+      expect(reopened.latest()).toBe(7);
+      expect(JSON.stringify(reopened.loadCheckpoint(7).toJSON())).toBe(saved[5]);
+    } finally {
+      rmSync(dir, { recursive: true, force: true });
+    }
+  });
+
+  it('should summarise ticks and rebuild the graph at any closed tick', () => {
+    const h = erdosRenyi(6, 0.5, { seed: 3 });
+    const log = new EvolutionLog(h, { snapshotEvery: 3 });
+    const expected: string[] = [];
+    const edgeIds: string[][] = [];
+    const close = () => {
+      log.tick();
+      expected.push(graphDigest(h).root);
+      edgeIds.push(h.edges.map((e) => e.id).sort());
+    };
+
+    h.entangle('n0', 'n1', 0.5);
+    h.setCoherence('n0', 0.4);
+    close();
+    h.removeNode('n2');
+    h.addNode('n6', { born: 1 });
+    h.addEdge(new Set(['n6', 'n0']), 0.3);
+    close();
+    h.applyBatch([
+      { op: 'removeNode', id: 'n0' },
+      { op: 'addNode', id: 'n7' },
+      { op: 'setState', id: 'n6', data: { born: 2 } },
+      { op: 'adjustCoherence', id: 'n1', delta: -0.2 },
+      { op: 'addEdge', nodes: ['n7', 'n1', 'n6'] },
+    ]);
+    close();
+    h.addNode('n0');
+    h.entangle('n0', 'n7', -0.4);
+    h.propagateCoherence();
+    close();
+    h.transaction((g, abort) => {
+      g.removeNode('n1');
+      abort();
+    });
+    h.disentangle('n0', 'n7');
+    close();
+    h.updateNode('n3', { mark: true });
+    close();
+
+    for (let t = 0; t < expected.length; t++) {
+      const g = log.graphAt(t);
+      expect(graphDigest(g).root).toBe(expected[t]);
+      expect(g.edges.map((e) => e.id).sort()).toEqual(edgeIds[t]);
+    }
+    expect(log.summaryAt(1)).toMatchObject({ created: ['n6'], destroyed: ['n2'], nodeCount: 6 });
+    expect(log.summaryAt(2)).toMatchObject({ created: ['n7'], destroyed: ['n0'] });
+    expect(log.summaries(0, 0)[0].minCoherence).toBeCloseTo(0.4, 10);
+    expect(log.summaries()).toHaveLength(6);
+    expect(() => log.graphAt(6)).toThrow(ArkheError);
+  });
+
+  it('should fold a record stream and rebuild, compact and branch at any offset', () => {
+    const journal = new GraphJournal();
+    journal.append(
+      { type: 'NodeAdded', id: 'a', data: { level: 1 } },
+      { type: 'NodeAdded', id: 'b', data: {}, coherence: 0.6 },
+      { type: 'NodeAdded', id: 'c', data: {} },
+      { type: 'EdgeAdded', id: 'e1', nodes: ['a', 'b', 'c'], weight: 0.5 },
+      { type: 'Entangled', a: 'a', b: 'b', strength: 0.5 },
+    );
+    const mid = journal.append({ type: 'CoherenceSet', id: 'a', coherence: 0.2 });
+    journal.append(
+      { type: 'StateSet', id: 'a', data: { level: 2 } },
+      { type: 'NodeRemoved', id: 'c' },
+    );
+    expect(journal.graph.nodes.get('b')?.coherence).toBe(0.6);
+    expect(Array.from(journal.graph.edges[0].nodes)).toEqual(['a', 'b']);
+    expect(journal.rebuild().toJSON()).toEqual(journal.graph.toJSON());
+    expect(journal.rebuild(3).edges).toHaveLength(0);
+    expect(journal.rebuild(mid).nodes.get('a')?.data).toEqual({ level: 1 });
+
+    const offset = journal.offset;
+    expect(() => journal.append({ type: 'EdgeRemoved', id: 'missing' })).toThrow(ArkheError);
+    expect(journal.offset).toBe(offset);
+
+    const branch = journal.branch(mid);
+    branch.append({ type: 'Disentangled', a: 'a', b: 'b' });
+    expect(branch.graph.nodes.has('c')).toBe(true);
+    expect(journal.graph.entanglements()).toHaveLength(1);
+
+    const restored = GraphJournal.fromJSON(JSON.parse(JSON.stringify(journal)));
+    expect(restored.graph.toJSON()).toEqual(journal.graph.toJSON());
+
+    journal.compact(mid);
+    expect(journal.start).toBe(mid);
+    expect(journal.offset).toBe(offset);
+    expect(journal.rebuild().toJSON()).toEqual(journal.graph.toJSON());
+    expect(journal.rebuild(mid).nodes.get('a')?.coherence).toBe(0.2);
+    expect(() => journal.rebuild(mid - 1)).toThrow('outside');
+  });
+
+  it('should build from a record stream with forward edge references', async () => {
+    let pulled = 0;
+    async function* dump() {
+      const records = [
+        { kind: 'edge' as const, nodes: ['a', 'b', 'c'], weight: 0.5 },
+        { kind: 'node' as const, id: 'a' },
+        { kind: 'edge' as const, nodes: ['a', 'b'], sources: ['a'] },
+        { kind: 'node' as const, id: 'b', coherence: 0.3 },
+        { kind: 'node' as const, id: 'c' },
+      ];
+      for (const record of records) {
+        pulled++;
+        yield record;
+      }
+    }
+    const h = await buildFromStream(dump());
+    expect(pulled).toBe(5);
+    expect(h.edges.map((e) => e.nodes.size).sort()).toEqual([2, 3]);
+    expect(h.edges.find((e) => e.sources)?.sources).toEqual(new Set(['a']));
+    expect(h.nodes.get('b')?.coherence).toBe(0.3);
+
+    const directed = await buildFromStream([
+      { kind: 'node', id: 'b' },
+      { kind: 'edge', nodes: ['b'], sources: ['a'] },
+      { kind: 'node', id: 'a' },
+    ]);
+    expect(directed.edges[0].sources).toEqual(new Set(['a']));
+    expect(directed.edges[0].nodes).toEqual(new Set(['a', 'b']));
+
+    await expect(buildFromStream([{ kind: 'edge', nodes: ['x', 'y'] }])).rejects.toThrow(ArkheError);
+    await expect(buildFromStream(
+      [{ kind: 'edge', nodes: ['x', 'y'] }, { kind: 'edge', nodes: ['x', 'z'] }],
+      { maxPending: 1 },
+    )).rejects.toThrow('waiting');
+  });
+
+  it('should round-trip coherence and edge ids through JSON', () => {
+    const h = new Hypergraph();
+    h.addNode('node1', { type: 'test' });
+    h.addNode('node2');
+    const edge = h.addEdge(new Set(['node1', 'node2']), 0.3);
+    h.bootstrapStep();
+
+    const restored = Hypergraph.fromJSON(JSON.parse(JSON.stringify(h.toJSON())));
+    expect(restored.nodes.get('node1')?.coherence).toBe(0.3);
+    expect(restored.nodes.get('node1')?.data.type).toBe('test');
+    expect(restored.edges[0].id).toBe(edge.id);
+
+    const emitted: string[] = [];
+    const copy = new Hypergraph();
+    copy.events.subscribe((e) => {
+      if (e.type === 'EdgeAdded') emitted.push(e.edge.id);
+    });
+    copy.addNode('node1');
+    copy.addNode('node2');
+    copy.addEdge(new Set(['node1', 'node2']), 0.3, undefined, edge.id);
+    expect(emitted).toEqual([edge.id]);
+    expect(() => copy.addEdge(new Set(['node1', 'node2']), 0.3, undefined, edge.id)).toThrow(ArkheError);
+  });
+
+  it('should label connected components and follow edge direction', () => {
+    const h = new Hypergraph();
+    for (const id of ['a', 'b', 'c', 'd', 'e']) h.addNode(id);
+    h.addEdge(new Set(['a', 'b']));
+    h.addDirectedEdge(new Set(['b']), new Set(['c']));
+    h.addEdge(new Set(['d', 'e']));
+
+    const { labels, components } = connectedComponents(h);
+    expect(components).toHaveLength(2);
+    expect(labels.get('a')).toBe(labels.get('c'));
+    expect(labels.get('a')).not.toBe(labels.get('d'));
+    expect(isConnected(h)).toBe(false);
+    expect(Array.from(reachableFrom(h, 'a')).sort()).toEqual(['a', 'b', 'c']);
+    expect(Array.from(reachableFrom(h, 'c'))).toEqual(['c']);
+  });
+
+  it('should migrate versioned hypergraph envelopes on load', () => {
+    const legacy = {
+      nodes: { a: { type: 'psi' }, b: { type: 'phi' } },
+      edges: [{ nodes: ['a', 'b'], weight: 0.4 }],
+    };
+    const upgraded = loadEnvelope(legacy);
+    expect(upgraded.edges[0].weight).toBe(0.4);
+
+    const degenerate = loadEnvelope({
+      nodes: { a: {}, b: {} },
+      edges: [{ nodes: ['a'], weight: 0.5 }, { nodes: [], weight: 1 }, { nodes: ['a', 'b'], weight: 0.2 }],
+    });
+    expect(degenerate.edges.map((e) => e.weight)).toEqual([0.2]);
+
+    upgraded.setCoherence('a', 0.7);
+    const saved = saveEnvelope(upgraded);
+    expect(saved.version).toBe(HYPERGRAPH_FORMAT_VERSION);
+    expect(loadEnvelope(JSON.parse(JSON.stringify(saved))).nodes.get('a')?.coherence).toBe(0.7);
+
+    const renamePsi = {
+      from: 2,
+      to: 3,
+      migrate: (state: Record<string, unknown>) => {
+        const nodes = state.nodes as Record<string, { type?: string }>;
+        for (const data of Object.values(nodes)) {
+          if (data.type === 'psi') data.type = 'wavefunction';
+        }
+        return state;
+      },
+    };
+    const renamed = loadEnvelope(legacy, { migrations: [renamePsi] });
+    expect(renamed.nodes.get('a')?.data.type).toBe('wavefunction');
+    expect(legacy.nodes.a.type).toBe('psi');
+
+    expect(() => loadEnvelope({ ...saved, version: 9 })).toThrow(ArkheError);
+    expect(() => loadEnvelope(saveEnvelope(upgraded, { version: 3 }))).toThrow('newer');
+  });
+
   it('should handle Silicon Constitution audit', () => {
     const h = new Hypergraph();
     h.addNode('Arquiteto', { type: 'human' });
@@ -0,0 +1,122 @@
+import { ArkheError } from './errors.js';
+import { Hypergraph } from './hypergraph.js';
+import type { ArkheNodeData } from './types.js';
+
+interface PendingEdge {
+  nodeIds: string[];
+  weight: number;
+  label?: string;
+}
+
+/**
+ * Fluent construction of a Hypergraph. Declarations are collected first and
+ * validated together at build(), so duplicate IDs are reported instead of
+ * silently overwriting an earlier node.
+ */
+export class HypergraphBuilder {
+  private nodeDecls: Array<{ id: string; data: ArkheNodeData }> = [];
+  private edgeDecls: PendingEdge[] = [];
+  private currentType?: string;
+
+  /** Tag subsequently declared nodes with this data.type unless they set their own. */
+  public withType(type: string | undefined): this {
+    this.currentType = type;
+    return this;
+  }
+
+  public node(id: string, data: ArkheNodeData = {}): this {
+    const typed = this.currentType !== undefined && data.type === undefined
+      ? { ...data, type: this.currentType }
+      : data;
+    this.nodeDecls.push({ id, data: typed });
+    return this;
+  }
+
+  public edge(nodeIds: Iterable<string>, weight: number = 1.0, label?: string): this {
+    this.edgeDecls.push({ nodeIds: Array.from(nodeIds), weight, label });
+    return this;
+  }
+
+  public build(): Hypergraph {
+    const seen = new Set<string>();
+    const duplicates = new Set<string>();
+    for (const { id } of this.nodeDecls) {
+      if (seen.has(id)) duplicates.add(id);
+      seen.add(id);
+    }
+    if (duplicates.size > 0) {
+      throw new ArkheError('DUPLICATE_ID', `Duplicate node IDs: ${Array.from(duplicates).join(', ')}`);
+    }
+
+    const h = new Hypergraph();
+    for (const { id, data } of this.nodeDecls) {
+      h.addNode(id, data);
+    }
+    for (const { nodeIds, weight, label } of this.edgeDecls) {
+      h.addEdge(new Set(nodeIds), weight, label);
+    }
+    return h;
+  }
+}
+
+/** One element of a streamed graph dump. A directed edge's `nodes` may list only its targets. */
+export type GraphRecord =
+  | { kind: 'node'; id: string; data?: ArkheNodeData; coherence?: number }
+  | { kind: 'edge'; nodes: string[]; weight?: number; label?: string; sources?: string[] };
+
+export interface StreamOptions {
+  /** Most edges that may wait for not-yet-seen nodes at once. Defaults to 100000. */
+  maxPending?: number;
+}
+
+type EdgeRecord = Extract<GraphRecord, { kind: 'edge' }>;
+
+/**
+ * Build a hypergraph from a (possibly async) stream of records, pulling one
+ * record at a time so producers are never read ahead of construction. Edges
+ * may precede their nodes: they are held until every endpoint has arrived,
+ * and any still waiting when the stream ends are reported as missing nodes.
+ */
+export async function buildFromStream(
+  records: AsyncIterable<GraphRecord> | Iterable<GraphRecord>,
+  options: StreamOptions = {},
+): Promise<Hypergraph> {
+  const maxPending = options.maxPending ?? 100_000;
+  const h = new Hypergraph();
+  // Waiting edges keyed by one endpoint they still lack.
+  const waiting = new Map<string, EdgeRecord[]>();
+  let pending = 0;
+
+  const place = (record: EdgeRecord) => {
+    const missing = [...record.nodes, ...(record.sources ?? [])].find((nid) => !h.nodes.has(nid));
+    if (missing === undefined) {
+      h.insertEdge(record.nodes, record.sources, record.weight, record.label);
+      return;
+    }
+    if (pending >= maxPending) {
+      throw new ArkheError('INVALID_FORMAT', `More than ${maxPending} edges are waiting for missing nodes`);
+    }
+    pending++;
+    if (!waiting.has(missing)) waiting.set(missing, []);
+    waiting.get(missing)!.push(record);
+  };
+
+  for await (const record of records) {
+    if (record.kind === 'node') {
+      h.addNode(record.id, record.data ?? {});
+      if (record.coherence !== undefined) h.setCoherence(record.id, record.coherence);
+      const ready = waiting.get(record.id) ?? [];
+      waiting.delete(record.id);
+      pending -= ready.length;
+      for (const edge of ready) place(edge);
+    } else {
+      place(record);
+    }
+  }
+
+  if (waiting.size > 0) {
+    const missing = Array.from(waiting.keys());
+    throw new ArkheError('NODE_NOT_FOUND', `Edges reference missing nodes: ${missing.join(', ')}`, missing[0]);
+  }
+  return h;
+}
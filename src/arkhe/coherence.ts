@@ -1,4 +1,5 @@
 import { Hypergraph } from './hypergraph.js';
+import type { ArkheNode } from './types.js';
 
 export function coherenceTotal(h: Hypergraph): number {
   return h.totalCoherence();
@@ -8,3 +9,34 @@ export function coherenceLocal(h: Hypergraph, nodeId: string): number {
   const node = h.nodes.get(nodeId);
   return node ? node.coherence : 0.0;
 }
+
+/** Per-tick coherence dynamics: a node's coherence after one tick. */
+export interface CoherenceModel {
+  next(node: ArkheNode, h: Hypergraph): number;
+}
+
+/** Lose a fraction `rate` of coherence each tick. */
+export function exponentialDecay(rate: number): CoherenceModel {
+  return { next: (node) => node.coherence * (1 - rate) };
+}
+
+/** Close a fraction `rate` of the gap to `baseline` each tick. */
+export function regenerateToward(baseline: number, rate: number): CoherenceModel {
+  return { next: (node) => node.coherence + (baseline - node.coherence) * rate };
+}
+
+/** Apply models one after another within a single tick. */
+export function composeModels(...models: CoherenceModel[]): CoherenceModel {
+  return {
+    next: (node, h) => models.reduce((c, m) => m.next({ ...node, coherence: c }, h), node.coherence),
+  };
+}
+
+/**
+ * Advance every node by one tick of `model`. All next values are computed
+ * first and applied together through setCoherences, so results are clamped
+ * to [0, 1] and each entangled partner shifts once by its link to the change.
+ */
+export function stepCoherence(h: Hypergraph, model: CoherenceModel): void {
+  h.setCoherences(new Map(Array.from(h.nodes.values(), (node) => [node.id, model.next(node, h)])));
+}
@@ -0,0 +1,102 @@
+import { ArkheError } from './errors.js';
+import { Hypergraph } from './hypergraph.js';
+import type { ArkheNode, ArkheNodeData } from './types.js';
+
+export interface WeightedState {
+  data: ArkheNodeData;
+  weight: number;
+}
+
+/** Combines weighted states into one; weights are non-negative and sum to 1. */
+export type Aggregator = (states: WeightedState[]) => ArkheNodeData;
+
+export interface ConsensusOptions {
+  /** Write the result into this existing node's data. */
+  target?: string;
+  /** Create a node with this ID holding the result, at the group's mean coherence. */
+  createAs?: string;
+}
+
+/**
+ * Weighted mean of numeric fields and of numeric arrays (element-wise).
+ * Fields are taken from `keys`, or every numeric field when omitted;
+ * non-numeric fields are copied from the heaviest state.
+ */
+export function meanAggregator(...keys: string[]): Aggregator {
+  return (states) => {
+    const heaviest = states.reduce((a, b) => (b.weight > a.weight ? b : a));
+    const result: ArkheNodeData = { ...heaviest.data };
+    const fields = keys.length > 0
+      ? keys
+      : Array.from(new Set(states.flatMap((s) => Object.keys(s.data))));
+    for (const key of fields) {
+      const values = states.map((s) => s.data[key]);
+      if (values.every((v) => typeof v === 'number')) {
+        result[key] = states.reduce((sum, s) => sum + s.weight * (s.data[key] as number), 0);
+      } else if (values.every((v) => Array.isArray(v) && v.every((x) => typeof x === 'number'))) {
+        const length = Math.min(...values.map((v) => (v as number[]).length));
+        result[key] = Array.from({ length }, (_, i) =>
+          states.reduce((sum, s) => sum + s.weight * (s.data[key] as number[])[i], 0));
+      }
+    }
+    return result;
+  };
+}
+
+/**
+ * Coherence-weighted vote. With a key, only that field is voted on and the
+ * rest is copied from the heaviest state; without one, whole states vote.
+ * Values are compared by their JSON encoding.
+ */
+export function majorityAggregator(key?: string): Aggregator {
+  return (states) => {
+    const tally = new Map<string, { weight: number; state: WeightedState }>();
+    for (const s of states) {
+      const vote = JSON.stringify(key === undefined ? s.data : s.data[key]);
+      const entry = tally.get(vote);
+      if (entry) entry.weight += s.weight;
+      else tally.set(vote, { weight: s.weight, state: s });
+    }
+    const winner = Array.from(tally.values()).reduce((a, b) => (b.weight > a.weight ? b : a));
+    if (key === undefined) return { ...winner.state.data };
+    const heaviest = states.reduce((a, b) => (b.weight > a.weight ? b : a));
+    return { ...heaviest.data, [key]: winner.state.data[key] };
+  };
+}
+
+/**
+ * Combine the states of a node group, weighted by coherence (uniformly when
+ * every node has zero coherence), and optionally store the result.
+ */
+export function consensus(
+  h: Hypergraph,
+  nodeIds: Iterable<string>,
+  aggregator: Aggregator,
+  options: ConsensusOptions = {},
+): ArkheNodeData {
+  const group: ArkheNode[] = [];
+  for (const id of nodeIds) {
+    const node = h.nodes.get(id);
+    if (!node) throw ArkheError.nodeNotFound(id);
+    group.push(node);
+  }
+  if (group.length === 0) {
+    throw new ArkheError('EMPTY_SELECTION', 'Consensus needs at least one node');
+  }
+
+  const total = group.reduce((sum, n) => sum + Math.max(0, n.coherence), 0);
+  const states = group.map((n) => ({
+    data: n.data,
+    weight: total > 0 ? Math.max(0, n.coherence) / total : 1 / group.length,
+  }));
+  const result = aggregator(states);
+
+  if (options.target !== undefined) {
+    h.setNodeData(options.target, result);
+  }
+  if (options.createAs !== undefined) {
+    const node = h.addNode(options.createAs, structuredClone(result));
+    h.setCoherence(node.id, group.reduce((sum, n) => sum + n.coherence, 0) / group.length);
+  }
+  return result;
+}
@@ -0,0 +1,81 @@
+import { ArkheError } from './errors.js';
+import { Hypergraph } from './hypergraph.js';
+import type { ArkheHyperedge } from './types.js';
+
+export interface DisjointUnionOptions {
+  leftPrefix?: string;
+  rightPrefix?: string;
+}
+
+export interface ProductOptions {
+  /** Joins the two component IDs of a product node. */
+  separator?: string;
+  /** Coherence of a product node from its components. Defaults to multiplication. */
+  combineCoherence?: (left: number, right: number) => number;
+}
+
+function copyEdge(
+  target: Hypergraph,
+  edge: ArkheHyperedge,
+  rename: (nid: string) => string,
+): ArkheHyperedge {
+  const sources = edge.sources && Array.from(edge.sources, rename);
+  return target.insertEdge(Array.from(edge.nodes, rename), sources, edge.weight, edge.label);
+}
+
+/**
+ * Place both graphs side by side in a new graph. IDs are prefixed per side
+ * so they cannot collide; passing empty prefixes requires disjoint IDs.
+ */
+export function disjointUnion(a: Hypergraph, b: Hypergraph, options: DisjointUnionOptions = {}): Hypergraph {
+  const leftPrefix = options.leftPrefix ?? 'left:';
+  const rightPrefix = options.rightPrefix ?? 'right:';
+  const result = new Hypergraph();
+
+  for (const [graph, prefix] of [[a, leftPrefix], [b, rightPrefix]] as const) {
+    for (const node of graph.nodes.values()) {
+      const id = prefix + node.id;
+      if (result.nodes.has(id)) {
+        throw new ArkheError('DUPLICATE_ID', `Node ${id} exists on both sides of the union`, id);
+      }
+      result.addNode(id, structuredClone(node.data));
+      result.setCoherence(id, node.coherence);
+    }
+    for (const edge of graph.edges) {
+      copyEdge(result, edge, (nid) => prefix + nid);
+    }
+  }
+  return result;
+}
+
+/**
+ * Cartesian product: one node per (u, v) pair with data { left, right }.
+ * Each edge of `a` is copied once per node of `b` and vice versa.
+ */
+export function product(a: Hypergraph, b: Hypergraph, options: ProductOptions = {}): Hypergraph {
+  const separator = options.separator ?? '×';
+  const combine = options.combineCoherence ?? ((l: number, r: number) => l * r);
+  const pairId = (u: string, v: string) => `${u}${separator}${v}`;
+  const result = new Hypergraph();
+
+  for (const u of a.nodes.values()) {
+    for (const v of b.nodes.values()) {
+      const node = result.addNode(pairId(u.id, v.id), {
+        left: structuredClone(u.data),
+        right: structuredClone(v.data),
+      });
+      result.setCoherence(node.id, combine(u.coherence, v.coherence));
+    }
+  }
+  for (const edge of a.edges) {
+    for (const v of b.nodes.keys()) {
+      copyEdge(result, edge, (u) => pairId(u, v));
+    }
+  }
+  for (const edge of b.edges) {
+    for (const u of a.nodes.keys()) {
+      copyEdge(result, edge, (v) => pairId(u, v));
+    }
+  }
+  return result;
+}
@@ -0,0 +1,146 @@
+import { isDeepStrictEqual } from 'util';
+import { Hypergraph } from './hypergraph.js';
+import type { ArkheEntanglement, ArkheHyperedge, ArkheNodeData } from './types.js';
+
+/** Distance between two node states; 0 means unchanged. */
+export type StateDistance = (before: ArkheNodeData, after: ArkheNodeData) => number;
+
+export interface DiffOptions {
+  /** Defaults to 0 for deeply equal states and 1 otherwise. */
+  distance?: StateDistance;
+  /** States count as changed when their distance exceeds this. Defaults to 0. */
+  tolerance?: number;
+  /** Coherence deltas at or below this magnitude are not reported. Defaults to 0. */
+  coherenceTolerance?: number;
+}
+
+export interface StateChange {
+  nodeId: string;
+  before: ArkheNodeData;
+  after: ArkheNodeData;
+  distance: number;
+}
+
+export interface EdgeReweight {
+  before: ArkheHyperedge;
+  after: ArkheHyperedge;
+}
+
+export interface EntanglementChange {
+  a: string;
+  b: string;
+  before: number;
+  after: number;
+}
+
+export interface GraphDiff {
+  addedNodes: string[];
+  removedNodes: string[];
+  changedStates: StateChange[];
+  coherenceDeltas: Record<string, number>; // after minus before, for nodes in both graphs
+  addedEdges: ArkheHyperedge[];
+  removedEdges: ArkheHyperedge[];
+  reweightedEdges: EdgeReweight[];
+  addedEntanglements: ArkheEntanglement[];
+  removedEntanglements: ArkheEntanglement[];
+  /** Links present in both graphs whose strength differs. */
+  changedEntanglements: EntanglementChange[];
+}
+
+/** Edges are matched by members, sources and label; generated IDs are ignored. */
+function edgeKey(edge: ArkheHyperedge): string {
+  return JSON.stringify([
+    Array.from(edge.nodes).sort(),
+    edge.sources ? Array.from(edge.sources).sort() : null,
+    edge.label ?? null,
+  ]);
+}
+
+function groupEdges(h: Hypergraph): Map<string, ArkheHyperedge[]> {
+  const groups = new Map<string, ArkheHyperedge[]>();
+  for (const edge of h.edges) {
+    const key = edgeKey(edge);
+    if (!groups.has(key)) groups.set(key, []);
+    groups.get(key)!.push(edge);
+  }
+  return groups;
+}
+
+/** Everything that changed going from `before` to `after`. */
+export function diff(before: Hypergraph, after: Hypergraph, options: DiffOptions = {}): GraphDiff {
+  const distance = options.distance ?? ((a: ArkheNodeData, b: ArkheNodeData) => (isDeepStrictEqual(a, b) ? 0 : 1));
+  const tolerance = options.tolerance ?? 0;
+  const coherenceTolerance = options.coherenceTolerance ?? 0;
+  const result: GraphDiff = {
+    addedNodes: [],
+    removedNodes: [],
+    changedStates: [],
+    coherenceDeltas: {},
+    addedEdges: [],
+    removedEdges: [],
+    reweightedEdges: [],
+    addedEntanglements: [],
+    removedEntanglements: [],
+    changedEntanglements: [],
+  };
+
+  for (const node of before.nodes.values()) {
+    const next = after.nodes.get(node.id);
+    if (!next) {
+      result.removedNodes.push(node.id);
+      continue;
+    }
+    const d = distance(node.data, next.data);
+    if (d > tolerance) {
+      result.changedStates.push({ nodeId: node.id, before: node.data, after: next.data, distance: d });
+    }
+    const delta = next.coherence - node.coherence;
+    if (Math.abs(delta) > coherenceTolerance) {
+      result.coherenceDeltas[node.id] = delta;
+    }
+  }
+  for (const id of after.nodes.keys()) {
+    if (!before.nodes.has(id)) result.addedNodes.push(id);
+  }
+
+  // Parallel edges with the same key pair up in order.
+  const remaining = groupEdges(after);
+  for (const [key, edges] of groupEdges(before)) {
+    const matches = remaining.get(key) ?? [];
+    edges.forEach((edge, i) => {
+      const match = matches[i];
+      if (!match) result.removedEdges.push(edge);
+      else if (match.weight !== edge.weight) result.reweightedEdges.push({ before: edge, after: match });
+    });
+    result.addedEdges.push(...matches.slice(edges.length));
+    remaining.delete(key);
+  }
+  for (const edges of remaining.values()) result.addedEdges.push(...edges);
+
+  const links = new Map(after.entanglements().map((l) => [`${l.a}\u0000${l.b}`, l]));
+  for (const link of before.entanglements()) {
+    const key = `${link.a}\u0000${link.b}`;
+    const match = links.get(key);
+    if (!match) result.removedEntanglements.push(link);
+    else if (match.strength !== link.strength) {
+      result.changedEntanglements.push({ a: link.a, b: link.b, before: link.strength, after: match.strength });
+    }
+    links.delete(key);
+  }
+  result.addedEntanglements.push(...links.values());
+  return result;
+}
+
+/** True when the diff records no change at all. */
+export function isEmptyDiff(d: GraphDiff): boolean {
+  return d.addedNodes.length === 0
+    && d.removedNodes.length === 0
+    && d.changedStates.length === 0
+    && Object.keys(d.coherenceDeltas).length === 0
+    && d.addedEdges.length === 0
+    && d.removedEdges.length === 0
+    && d.reweightedEdges.length === 0
+    && d.addedEntanglements.length === 0
+    && d.removedEntanglements.length === 0
+    && d.changedEntanglements.length === 0;
+}
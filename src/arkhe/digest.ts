@@ -0,0 +1,92 @@
+import { createHash } from 'crypto';
+import { Hypergraph } from './hypergraph.js';
+import type { ArkheEntanglement, ArkheHyperedge, ArkheNode, ArkheNodeData } from './types.js';
+
+/** Stable string encoding of a node state; equal states must encode identically. */
+export type StateEncoder = (data: ArkheNodeData) => string;
+
+export interface DigestOptions {
+  /** Defaults to canonicalJson. */
+  encodeState?: StateEncoder;
+  /** Fold coherence into node hashes. Defaults to true. */
+  includeCoherence?: boolean;
+}
+
+export interface GraphDigest {
+  /** Merkle root over node, edge and entanglement hashes. */
+  root: string;
+  nodeRoot: string;
+  edgeRoot: string;
+  entanglementRoot: string;
+  /** Leaf hash per node ID. */
+  nodes: Map<string, string>;
+}
+
+function sha256(text: string): string {
+  return createHash('sha256').update(text).digest('hex');
+}
+
+/** JSON with object keys sorted, so key order does not change the encoding. */
+export function canonicalJson(value: unknown): string {
+  return JSON.stringify(value, (_key, v) => {
+    if (v === null || typeof v !== 'object' || Array.isArray(v)) return v;
+    return Object.fromEntries(Object.keys(v).sort().map((k) => [k, v[k]]));
+  });
+}
+
+export function nodeHash(node: ArkheNode, options: DigestOptions = {}): string {
+  const encode = options.encodeState ?? canonicalJson;
+  const coherence = (options.includeCoherence ?? true) ? node.coherence : null;
+  return sha256(JSON.stringify([node.id, encode(node.data), coherence]));
+}
+
+/** Edges hash by structure and weight; generated IDs are ignored. */
+function edgeHash(edge: ArkheHyperedge): string {
+  return sha256(JSON.stringify([
+    Array.from(edge.nodes).sort(),
+    edge.sources ? Array.from(edge.sources).sort() : null,
+    edge.label ?? null,
+    edge.weight,
+  ]));
+}
+
+/** Links hash by their (ordered) pair and strength. */
+function entanglementHash(link: ArkheEntanglement): string {
+  return sha256(JSON.stringify([link.a, link.b, link.strength]));
+}
+
+/** Pairwise-hash leaves up to a single root; an odd node is carried up unchanged. */
+export function merkleRoot(leaves: string[]): string {
+  if (leaves.length === 0) return sha256('');
+  let level = leaves;
+  while (level.length > 1) {
+    const next: string[] = [];
+    for (let i = 0; i < level.length; i += 2) {
+      next.push(i + 1 < level.length ? sha256(level[i] + level[i + 1]) : level[i]);
+    }
+    level = next;
+  }
+  return level[0];
+}
+
+/**
+ * Content digest of the whole graph. Leaves are ordered by node ID (and by
+ * hash for edges and links), so insertion order does not matter.
+ */
+export function graphDigest(h: Hypergraph, options: DigestOptions = {}): GraphDigest {
+  const nodes = new Map<string, string>();
+  for (const id of Array.from(h.nodes.keys()).sort()) {
+    nodes.set(id, nodeHash(h.nodes.get(id)!, options));
+  }
+  const nodeRoot = merkleRoot(Array.from(nodes.values()));
+  const edgeRoot = merkleRoot(h.edges.map(edgeHash).sort());
+  const entanglementRoot = merkleRoot(h.entanglements().map(entanglementHash).sort());
+  return { root: sha256(nodeRoot + edgeRoot + entanglementRoot), nodeRoot, edgeRoot, entanglementRoot, nodes };
+}
+
+/** Node IDs whose hashes differ between two digests, including nodes present on one side only. */
+export function divergentNodes(a: GraphDigest, b: GraphDigest): string[] {
+  if (a.nodeRoot === b.nodeRoot) return [];
+  const ids = new Set([...a.nodes.keys(), ...b.nodes.keys()]);
+  return Array.from(ids).filter((id) => a.nodes.get(id) !== b.nodes.get(id)).sort();
+}
@@ -0,0 +1,101 @@
+import { ArkheError } from './errors.js';
+import { Hypergraph } from './hypergraph.js';
+import type { HypergraphState } from './types.js';
+
+/**
+ * Layout version written by saveEnvelope.
+ * 1: nodes and edges ({ nodes, weight }) only, as written by the first toJSON.
+ * 2: adds per-node coherence, edge ids/labels/sources, and entanglements.
+ */
+export const HYPERGRAPH_FORMAT_VERSION = 2;
+
+const FORMAT = 'arkhe-hypergraph';
+
+export interface HypergraphEnvelope {
+  format: typeof FORMAT;
+  version: number;
+  savedAt: string;
+  state: HypergraphState;
+}
+
+/** Upgrades a serialized state by exactly one version step. */
+export interface Migration {
+  from: number;
+  to: number;
+  migrate(state: Record<string, unknown>): Record<string, unknown>;
+}
+
+export const BUILTIN_MIGRATIONS: Migration[] = [
+  // Every field added in v2 is optional. v1 allowed edges over fewer than two
+  // nodes, which carry no coupling and are now rejected by addEdge; drop them.
+  {
+    from: 1,
+    to: 2,
+    migrate: (state) => {
+      const edges = (state.edges ?? []) as Array<{ nodes: string[] }>;
+      return { ...state, edges: edges.filter((e) => new Set(e.nodes).size >= 2) };
+    },
+  },
+];
+
+export interface SaveOptions {
+  /** Stamp a later, application-defined version. Defaults to HYPERGRAPH_FORMAT_VERSION. */
+  version?: number;
+}
+
+export interface LoadOptions {
+  /** Additional steps, e.g. renaming node types; taken before built-ins with the same `from`. */
+  migrations?: Migration[];
+  /** Defaults to the highest version any migration reaches. */
+  targetVersion?: number;
+}
+
+export function saveEnvelope(h: Hypergraph, options: SaveOptions = {}): HypergraphEnvelope {
+  return {
+    format: FORMAT,
+    version: options.version ?? HYPERGRAPH_FORMAT_VERSION,
+    savedAt: new Date().toISOString(),
+    state: h.toJSON(),
+  };
+}
+
+/**
+ * Load an envelope, upgrading older layouts through the migration chain.
+ * Bare states written before envelopes existed are treated as version 1.
+ */
+export function loadEnvelope(value: unknown, options: LoadOptions = {}): Hypergraph {
+  if (value === null || typeof value !== 'object') {
+    throw new ArkheError('INVALID_FORMAT', 'Serialized hypergraph must be an object');
+  }
+  const record = value as Record<string, unknown>;
+  let version: number;
+  let state: Record<string, unknown>;
+  if (record.format === FORMAT) {
+    version = Number(record.version);
+    state = record.state as Record<string, unknown>;
+  } else if ('nodes' in record && 'edges' in record) {
+    version = 1;
+    state = record;
+  } else {
+    throw new ArkheError('INVALID_FORMAT', 'Not a serialized hypergraph');
+  }
+
+  const migrations = [...(options.migrations ?? []), ...BUILTIN_MIGRATIONS];
+  const target = options.targetVersion
+    ?? Math.max(HYPERGRAPH_FORMAT_VERSION, ...migrations.map((m) => m.to));
+  if (!Number.isInteger(version)) {
+    throw new ArkheError('INVALID_FORMAT', `Invalid hypergraph version ${record.version}`);
+  }
+  if (version > target) {
+    throw new ArkheError('INVALID_FORMAT', `Hypergraph version ${record.version} is newer than supported ${target}`);
+  }
+  while (version < target) {
+    const step = migrations.find((m) => m.from === version);
+    if (!step || step.to <= version) {
+      throw new ArkheError('INVALID_FORMAT', `No migration from hypergraph version ${version}`);
+    }
+    state = step.migrate(structuredClone(state));
+    version = step.to;
+  }
+  return Hypergraph.fromJSON(state as unknown as HypergraphState);
+}
@@ -0,0 +1,34 @@
+export type ArkheErrorCode =
+  | 'NODE_NOT_FOUND'
+  | 'DUPLICATE_ID'
+  | 'INVALID_EDGE'
+  | 'EDGE_NOT_FOUND'
+  | 'INVALID_ENTANGLEMENT'
+  | 'SNAPSHOT_NOT_FOUND'
+  | 'CHECKPOINT_NOT_FOUND'
+  | 'INVALID_FORMAT'
+  | 'INVARIANT_VIOLATION'
+  | 'EMPTY_SELECTION'
+  | 'INVALID_ARGUMENT'
+  | 'SEARCH_LIMIT_EXCEEDED';
+
+/** Error raised by hypergraph operations; `code` is stable for programmatic handling. */
+export class ArkheError extends Error {
+  public readonly code: ArkheErrorCode;
+  public readonly nodeId?: string;
+
+  constructor(code: ArkheErrorCode, message: string, nodeId?: string) {
+    super(message);
+    this.name = 'ArkheError';
+    this.code = code;
+    this.nodeId = nodeId;
+  }
+
+  public static nodeNotFound(nodeId: string): ArkheError {
+    return new ArkheError('NODE_NOT_FOUND', `Node ${nodeId} does not exist`, nodeId);
+  }
+
+  public static duplicateId(nodeId: string): ArkheError {
+    return new ArkheError('DUPLICATE_ID', `Node ${nodeId} already exists`, nodeId);
+  }
+}
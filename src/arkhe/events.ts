@@ -0,0 +1,66 @@
+import type { ArkheNode, ArkheHyperedge, ArkheNodeData } from './types.js';
+
+/** Net node changes reported by bulk events; `changed` carries each node's data from before. */
+export interface NodeChanges {
+  created: ArkheNode[];
+  removed: ArkheNode[];
+  changed: Array<{ nodeId: string; previous: ArkheNodeData }>;
+}
+
+export type HypergraphEvent =
+  | { type: 'NodeAdded'; node: ArkheNode }
+  | { type: 'NodeRemoved'; node: ArkheNode }
+  | { type: 'EdgeAdded'; edge: ArkheHyperedge }
+  | { type: 'EdgeRemoved'; edge: ArkheHyperedge }
+  | { type: 'StateChanged'; nodeId: string; previous: ArkheNodeData; data: ArkheNodeData }
+  | { type: 'CoherenceUpdated'; nodeId: string; previous: number; coherence: number }
+  /** `snapshotId` is absent when a failed applyBatch was undone, which leaves no changes to report. */
+  | ({ type: 'GraphRestored'; snapshotId?: string } & NodeChanges)
+  /** Also the batch's net edge changes and the coherence moves of nodes present before and after it. */
+  | ({
+    type: 'BatchApplied';
+    mutations: number;
+    addedEdges: ArkheHyperedge[];
+    removedEdges: ArkheHyperedge[];
+    coherence: Array<{ nodeId: string; previous: number; coherence: number }>;
+  } & NodeChanges)
+  | { type: 'Reaped'; nodes: ArkheNode[] };
+
+export type HypergraphListener = (event: HypergraphEvent) => void;
+
+/** Typed callbacks for node lifecycle, registered with Hypergraph.registerHooks. */
+export interface LifecycleHooks {
+  onNodeCreated?(node: ArkheNode): void;
+  onNodeDestroyed?(node: ArkheNode): void;
+  onStateChanged?(node: ArkheNode, previous: ArkheNodeData): void;
+}
+
+/**
+ * Synchronous fan-out of graph mutations to subscribers.
+ * Listeners run in subscription order; a throwing listener does not stop the others.
+ */
+export class EventBus {
+  private listeners: Set<HypergraphListener> = new Set();
+
+  /** Register a listener. Returns a function that unsubscribes it. */
+  public subscribe(listener: HypergraphListener): () => void {
+    this.listeners.add(listener);
+    return () => {
+      this.listeners.delete(listener);
+    };
+  }
+
+  public emit(event: HypergraphEvent): void {
+    for (const listener of this.listeners) {
+      try {
+        listener(event);
+      } catch {
+        // Observers must never break graph mutation.
+      }
+    }
+  }
+
+  public get subscriberCount(): number {
+    return this.listeners.size;
+  }
+}
@@ -0,0 +1,220 @@
+import { ArkheError } from './errors.js';
+import { Hypergraph } from './hypergraph.js';
+import { applyRecord } from './journal.js';
+import type { HypergraphEvent } from './events.js';
+import type { GraphRecord } from './journal.js';
+import type { ArkheHyperedge, ArkheNode, HypergraphState } from './types.js';
+
+/** Net node changes and coherence aggregates over one tick. */
+export interface TickSummary {
+  tick: number;
+  /** Nodes present at the end of the tick but not at the end of the previous one. */
+  created: string[];
+  destroyed: string[];
+  nodeCount: number;
+  edgeCount: number;
+  meanCoherence: number;
+  minCoherence: number;
+  maxCoherence: number;
+}
+
+export interface EvolutionLogOptions {
+  /** Ticks between full snapshots that graphAt() replays from. Defaults to 100. */
+  snapshotEvery?: number;
+}
+
+function nodeAdded(node: ArkheNode): GraphRecord {
+  return { type: 'NodeAdded', id: node.id, data: node.data, coherence: node.coherence };
+}
+
+function edgeAdded(edge: ArkheHyperedge): GraphRecord {
+  return {
+    type: 'EdgeAdded',
+    id: edge.id,
+    nodes: Array.from(edge.nodes),
+    weight: edge.weight,
+    ...(edge.label !== undefined ? { label: edge.label } : {}),
+    ...(edge.sources ? { sources: Array.from(edge.sources) } : {}),
+  };
+}
+
+function linkKey(a: string, b: string): string {
+  return `${a}\u0000${b}`;
+}
+
+/**
+ * Graph-level history keyed by tick, from the graph's event bus. Ticks are
+ * advanced by the caller with tick(), as for NodeHistory; each call closes the
+ * current tick with a TickSummary. Changes are kept as journal records between
+ * full snapshots, so graphAt() restores the nearest snapshot and replays the
+ * ticks after it. Rollbacks are not itemised on the bus, so a tick containing
+ * one always ends in a snapshot. Edits that emit no event, such as assigning
+ * an edge's weight directly, are not seen.
+ */
+export class EvolutionLog {
+  private h: Hypergraph;
+  private snapshotEvery: number;
+  private ticks = 0;
+  private closed: TickSummary[] = [];
+  // Records of each closed tick, by tick; ticks ending in a snapshot keep none.
+  private records: GraphRecord[][] = [];
+  private snapshots: Map<number, HypergraphState> = new Map();
+  private base: HypergraphState;
+  private pending: GraphRecord[] = [];
+  private restored = false;
+  private removedThisTick: Set<string> = new Set();
+  private lastIds: Set<string>;
+  private lastLinks: Map<string, number>;
+  private unsubscribe: () => void;
+
+  constructor(h: Hypergraph, options: EvolutionLogOptions = {}) {
+    this.h = h;
+    this.snapshotEvery = Math.max(1, options.snapshotEvery ?? 100);
+    this.base = h.toJSON();
+    this.lastIds = new Set(h.nodes.keys());
+    this.lastLinks = this.currentLinks();
+    this.unsubscribe = h.events.subscribe((e) => this.apply(e));
+  }
+
+  /** The tick now being recorded. */
+  public get currentTick(): number {
+    return this.ticks;
+  }
+
+  /** Close the current tick and start the next; returns the closed tick's summary. */
+  public tick(): TickSummary {
+    const links = this.currentLinks();
+    if (!this.restored) {
+      // Entangling emits no event, so links are compared at the tick boundary.
+      // Replaying removeNode drops a removed node's links by itself.
+      const dropped = (key: string) => key.split('\u0000').some((id) => this.removedThisTick.has(id));
+      for (const [key, strength] of links) {
+        if (!dropped(key) && this.lastLinks.get(key) === strength) continue;
+        const [a, b] = key.split('\u0000');
+        this.pending.push({ type: 'Entangled', a, b, strength });
+      }
+      for (const key of this.lastLinks.keys()) {
+        if (links.has(key) || dropped(key)) continue;
+        const [a, b] = key.split('\u0000');
+        this.pending.push({ type: 'Disentangled', a, b });
+      }
+    }
+    this.lastLinks = links;
+
+    const ids = new Set(this.h.nodes.keys());
+    let min = Infinity;
+    let max = -Infinity;
+    for (const node of this.h.nodes.values()) {
+      min = Math.min(min, node.coherence);
+      max = Math.max(max, node.coherence);
+    }
+    const summary: TickSummary = {
+      tick: this.ticks,
+      created: Array.from(ids).filter((id) => !this.lastIds.has(id)),
+      destroyed: Array.from(this.lastIds).filter((id) => !ids.has(id)),
+      nodeCount: ids.size,
+      edgeCount: this.h.edges.length,
+      meanCoherence: this.h.totalCoherence(),
+      minCoherence: ids.size > 0 ? min : 0,
+      maxCoherence: ids.size > 0 ? max : 0,
+    };
+    this.closed.push(summary);
+    this.lastIds = ids;
+
+    if (this.restored || (this.ticks + 1) % this.snapshotEvery === 0) {
+      this.snapshots.set(this.ticks, this.h.toJSON());
+      this.records.push([]);
+    } else {
+      this.records.push(this.pending);
+    }
+    this.pending = [];
+    this.restored = false;
+    this.removedThisTick = new Set();
+    this.ticks++;
+    return summary;
+  }
+
+  /** Summaries of closed ticks in [from, to]. */
+  public summaries(from: number = 0, to: number = this.ticks - 1): TickSummary[] {
+    return this.closed.slice(Math.max(0, from), Math.max(0, to + 1));
+  }
+
+  /** Summary of a closed tick. */
+  public summaryAt(tick: number): TickSummary | undefined {
+    return this.closed[tick];
+  }
+
+  /** A fresh graph as of the end of a closed tick. */
+  public graphAt(tick: number): Hypergraph {
+    if (!Number.isInteger(tick) || tick < 0 || tick >= this.ticks) {
+      throw new ArkheError('INVALID_ARGUMENT', `Tick ${tick} has not been closed`);
+    }
+    let from = -1;
+    for (const t of this.snapshots.keys()) {
+      if (t <= tick && t > from) from = t;
+    }
+    const h = Hypergraph.fromJSON(from < 0 ? this.base : this.snapshots.get(from)!);
+    for (let t = from + 1; t <= tick; t++) {
+      for (const record of this.records[t]) applyRecord(h, record);
+    }
+    return h;
+  }
+
+  /** Stop recording. */
+  public detach(): void {
+    this.unsubscribe();
+  }
+
+  private currentLinks(): Map<string, number> {
+    return new Map(this.h.entanglements().map((l) => [linkKey(l.a, l.b), l.strength]));
+  }
+
+  private apply(event: HypergraphEvent): void {
+    if (this.restored) return;
+    const out = this.pending;
+    switch (event.type) {
+      case 'NodeAdded':
+        out.push(nodeAdded(event.node));
+        break;
+      case 'NodeRemoved':
+        out.push({ type: 'NodeRemoved', id: event.node.id });
+        this.removedThisTick.add(event.node.id);
+        break;
+      case 'EdgeAdded':
+        out.push(edgeAdded(event.edge));
+        break;
+      case 'EdgeRemoved':
+        out.push({ type: 'EdgeRemoved', id: event.edge.id });
+        break;
+      case 'StateChanged':
+        out.push({ type: 'StateSet', id: event.nodeId, data: event.data });
+        break;
+      case 'CoherenceUpdated':
+        out.push({ type: 'CoherenceSet', id: event.nodeId, coherence: event.coherence });
+        break;
+      case 'BatchApplied':
+        // Net changes, in an order that replays: edges out, nodes out, nodes in, then the rest.
+        for (const edge of event.removedEdges) out.push({ type: 'EdgeRemoved', id: edge.id });
+        for (const node of event.removed) {
+          out.push({ type: 'NodeRemoved', id: node.id });
+          this.removedThisTick.add(node.id);
+        }
+        for (const node of event.created) out.push(nodeAdded(node));
+        for (const { nodeId } of event.changed) {
+          const node = this.h.nodes.get(nodeId);
+          if (node) out.push({ type: 'StateSet', id: nodeId, data: node.data });
+        }
+        for (const { nodeId, coherence } of event.coherence) {
+          out.push({ type: 'CoherenceSet', id: nodeId, coherence });
+        }
+        for (const edge of event.addedEdges) out.push(edgeAdded(edge));
+        break;
+      case 'GraphRestored':
+        // A failed applyBatch leaves nothing to record.
+        if (event.snapshotId !== undefined) this.restored = true;
+        break;
+      default:
+        break;
+    }
+  }
+}
@@ -0,0 +1,137 @@
+import { Hypergraph } from './hypergraph.js';
+import type { ArkheNode } from './types.js';
+
+export interface DotOptions {
+  graphName?: string;
+  /** Fill nodes on a red (C=0) to green (C=1) scale. Defaults to true. */
+  colorByCoherence?: boolean;
+  /** Graphviz shape per node `data.type`; falls back to defaultShape. */
+  shapes?: Record<string, string>;
+  defaultShape?: string;
+}
+
+function dotEscape(value: string): string {
+  return value.replace(/\\/g, '\\\\').replace(/"/g, '\\"');
+}
+
+function coherenceColor(coherence: number): string {
+  const clamped = Math.max(0, Math.min(1, coherence));
+  // HSV hue 0 = red, 0.333 = green.
+  return `${(clamped / 3).toFixed(3)} 0.600 0.950`;
+}
+
+function nodeLabel(node: ArkheNode): string {
+  const parts = [node.id];
+  if (node.data.type) parts.push(String(node.data.type));
+  parts.push(`C=${node.coherence.toFixed(2)}`);
+  return parts.map(dotEscape).join('\\n');
+}
+
+/**
+ * Render the hypergraph as Graphviz DOT. Each hyperedge becomes a small
+ * auxiliary vertex linked to its member nodes, so n-ary edges stay readable.
+ */
+export function toDot(h: Hypergraph, options: DotOptions = {}): string {
+  const colorByCoherence = options.colorByCoherence ?? true;
+  const shapes = options.shapes ?? {};
+  const defaultShape = options.defaultShape ?? 'ellipse';
+
+  const lines: string[] = [`graph "${dotEscape(options.graphName ?? 'arkhe')}" {`];
+  for (const node of h.nodes.values()) {
+    const attrs = [
+      `label="${nodeLabel(node)}"`,
+      `shape="${dotEscape(shapes[node.data.type ?? ''] ?? defaultShape)}"`,
+    ];
+    if (colorByCoherence) {
+      attrs.push('style="filled"', `fillcolor="${coherenceColor(node.coherence)}"`);
+    }
+    lines.push(`  "${dotEscape(node.id)}" [${attrs.join(', ')}];`);
+  }
+
+  for (const edge of h.edges) {
+    const hub = `edge:${edge.id}`;
+    const label = edge.label ? `${edge.label} ` : '';
+    lines.push(
+      `  "${dotEscape(hub)}" [shape="point", xlabel="${dotEscape(`${label}w=${edge.weight.toFixed(2)}`)}"];`,
+    );
+    for (const nid of edge.nodes) {
+      if (!edge.sources) {
+        lines.push(`  "${dotEscape(hub)}" -- "${dotEscape(nid)}";`);
+      } else if (edge.sources.has(nid)) {
+        lines.push(`  "${dotEscape(nid)}" -- "${dotEscape(hub)}" [dir="forward"];`);
+      } else {
+        lines.push(`  "${dotEscape(hub)}" -- "${dotEscape(nid)}" [dir="forward"];`);
+      }
+    }
+  }
+  lines.push('}');
+  return lines.join('\n');
+}
+
+export interface MermaidOptions {
+  /** Flowchart direction. Defaults to 'LR'. */
+  direction?: 'LR' | 'RL' | 'TB' | 'BT';
+  /** Group nodes into one subgraph per `data.type`. Defaults to true. */
+  groupByType?: boolean;
+}
+
+function mermaidText(value: string): string {
+  return value.replace(/"/g, '#quot;');
+}
+
+/**
+ * Render the hypergraph as a Mermaid flowchart. Node IDs are replaced by
+ * positional identifiers (Mermaid restricts their syntax) and shown as labels.
+ * Two-node edges are drawn directly; larger hyperedges get an auxiliary hub.
+ */
+export function toMermaid(h: Hypergraph, options: MermaidOptions = {}): string {
+  const groupByType = options.groupByType ?? true;
+  const ids = new Map<string, string>();
+  for (const id of h.nodes.keys()) ids.set(id, `n${ids.size}`);
+
+  const declare = (node: ArkheNode) =>
+    `${ids.get(node.id)}["${mermaidText(node.id)}<br/>C=${node.coherence.toFixed(2)}"]`;
+
+  const lines = [`flowchart ${options.direction ?? 'LR'}`];
+  if (groupByType) {
+    const groups = new Map<string, ArkheNode[]>();
+    const untyped: ArkheNode[] = [];
+    for (const node of h.nodes.values()) {
+      if (node.data.type === undefined) {
+        untyped.push(node);
+        continue;
+      }
+      const type = String(node.data.type);
+      if (!groups.has(type)) groups.set(type, []);
+      groups.get(type)!.push(node);
+    }
+    let g = 0;
+    for (const [type, nodes] of groups) {
+      lines.push(`  subgraph g${g++}["${mermaidText(type)}"]`);
+      for (const node of nodes) lines.push(`    ${declare(node)}`);
+      lines.push('  end');
+    }
+    for (const node of untyped) lines.push(`  ${declare(node)}`);
+  } else {
+    for (const node of h.nodes.values()) lines.push(`  ${declare(node)}`);
+  }
+
+  h.edges.forEach((edge, i) => {
+    const text = mermaidText(`${edge.label ? `${edge.label} ` : ''}w=${edge.weight.toFixed(2)}`);
+    if (edge.nodes.size === 2) {
+      // Put the source first so directed links point at the target.
+      const [a, b] = Array.from(edge.nodes).sort((x, y) => Number(!edge.sources?.has(x)) - Number(!edge.sources?.has(y)));
+      lines.push(`  ${ids.get(a)} ${edge.sources ? '-->' : '---'}|"${text}"| ${ids.get(b)}`);
+      return;
+    }
+    const hub = `e${i}`;
+    lines.push(`  ${hub}(("${text}"))`);
+    for (const nid of edge.nodes) {
+      const member = ids.get(nid)!;
+      if (!edge.sources) lines.push(`  ${hub} --- ${member}`);
+      else if (edge.sources.has(nid)) lines.push(`  ${member} --> ${hub}`);
+      else lines.push(`  ${hub} --> ${member}`);
+    }
+  });
+  return lines.join('\n');
+}
@@ -0,0 +1,149 @@
+import { ArkheError } from './errors.js';
+import { Hypergraph } from './hypergraph.js';
+import type { ArkheNodeData } from './types.js';
+
+/** Uniform source on [0, 1). */
+export type Random = () => number;
+
+/** Deterministic PRNG (mulberry32): equal seeds give equal sequences. */
+export function seededRandom(seed: number): Random {
+  let a = seed >>> 0;
+  return () => {
+    a = (a + 0x6d2b79f5) >>> 0;
+    let t = a;
+    t = Math.imul(t ^ (t >>> 15), t | 1);
+    t ^= t + Math.imul(t ^ (t >>> 7), t | 61);
+    return ((t ^ (t >>> 14)) >>> 0) / 4294967296;
+  };
+}
+
+export interface GeneratorOptions {
+  /** Seed for seededRandom; ignored when `random` is given. Defaults to 0. */
+  seed?: number;
+  random?: Random;
+  /** Data of the i-th node. Defaults to {} (lattices default to { row, col }). */
+  state?: (index: number, random: Random) => ArkheNodeData;
+  /** Initial coherence per node. Defaults to 1. */
+  coherence?: (random: Random) => number;
+  /** Node IDs are `${idPrefix}${index}`. Defaults to 'n'. */
+  idPrefix?: string;
+}
+
+export interface RandomHypergraphOptions extends GeneratorOptions {
+  /** Nodes per hyperedge. Defaults to 2. */
+  edgeSize?: number;
+}
+
+export interface LatticeOptions extends GeneratorOptions {
+  /** Wrap rows and columns around into a torus. Defaults to false. */
+  periodic?: boolean;
+}
+
+// Degree-weighted draws per member before preferentialAttachment falls back to a sweep of the pool.
+const DRAWS_PER_MEMBER = 16;
+
+function edgeSizeOf(options: RandomHypergraphOptions): number {
+  const k = options.edgeSize ?? 2;
+  if (!Number.isInteger(k) || k < 2) {
+    throw new ArkheError('INVALID_ARGUMENT', `Hyperedges need at least 2 nodes, got edgeSize ${k}`);
+  }
+  return k;
+}
+
+interface Seeded {
+  h: Hypergraph;
+  ids: string[];
+  random: Random;
+}
+
+function seedNodes(
+  count: number,
+  options: GeneratorOptions,
+  defaultState: (index: number) => ArkheNodeData = () => ({}),
+): Seeded {
+  const random = options.random ?? seededRandom(options.seed ?? 0);
+  const prefix = options.idPrefix ?? 'n';
+  const h = new Hypergraph();
+  const ids: string[] = [];
+  for (let i = 0; i < count; i++) {
+    const data = options.state ? options.state(i, random) : defaultState(i);
+    const node = h.addNode(`${prefix}${i}`, data);
+    if (options.coherence) h.setCoherence(node.id, options.coherence(random));
+    ids.push(node.id);
+  }
+  return { h, ids, random };
+}
+
+/**
+ * Every `edgeSize`-subset of the n nodes becomes a hyperedge with probability
+ * p. All subsets are enumerated, so keep n small for edgeSize above 2.
+ */
+export function erdosRenyi(n: number, p: number, options: RandomHypergraphOptions = {}): Hypergraph {
+  const k = edgeSizeOf(options);
+  const { h, ids, random } = seedNodes(n, options);
+  const chosen: string[] = [];
+  const visit = (start: number) => {
+    if (chosen.length === k) {
+      if (random() < p) h.addEdge(new Set(chosen));
+      return;
+    }
+    for (let i = start; i <= n - (k - chosen.length); i++) {
+      chosen.push(ids[i]);
+      visit(i + 1);
+      chosen.pop();
+    }
+  };
+  visit(0);
+  return h;
+}
+
+/**
+ * Growth by preferential attachment: the first `edgeSize` nodes share one
+ * hyperedge, then each new node joins `m` hyperedges with `edgeSize - 1`
+ * existing nodes picked with probability proportional to degree. Should the
+ * draws keep hitting already-picked nodes, the rest are taken in pool order
+ * from a random offset, so a degenerate `random` cannot stall generation.
+ */
+export function preferentialAttachment(n: number, m: number, options: RandomHypergraphOptions = {}): Hypergraph {
+  const k = edgeSizeOf(options);
+  const { h, ids, random } = seedNodes(n, options);
+  const seedSize = Math.min(k, n);
+  if (seedSize >= 2) h.addEdge(new Set(ids.slice(0, seedSize)));
+
+  // One entry per edge membership, so uniform sampling is degree-weighted.
+  const pool: string[] = ids.slice(0, seedSize);
+  for (let i = seedSize; i < n; i++) {
+    const added: string[] = [];
+    for (let e = 0; e < m; e++) {
+      const members = new Set([ids[i]]);
+      const want = Math.min(k - 1, i);
+      for (let draws = want * DRAWS_PER_MEMBER; members.size < want + 1 && draws > 0; draws--) {
+        members.add(pool[Math.floor(random() * pool.length)]);
+      }
+      if (members.size < want + 1) {
+        // The pool holds all i earlier nodes, so one sweep always completes the edge.
+        const offset = Math.floor(random() * pool.length);
+        for (let j = 0; members.size < want + 1; j++) members.add(pool[(offset + j) % pool.length]);
+      }
+      h.addEdge(members);
+      added.push(...members);
+    }
+    pool.push(...added);
+  }
+  return h;
+}
+
+/** rows × cols grid with a hyperedge between each pair of orthogonal neighbours. */
+export function lattice(rows: number, cols: number, options: LatticeOptions = {}): Hypergraph {
+  const { h, ids } = seedNodes(rows * cols, options, (i) => ({ row: Math.floor(i / cols), col: i % cols }));
+  const at = (r: number, c: number) => ids[r * cols + c];
+  for (let r = 0; r < rows; r++) {
+    for (let c = 0; c < cols; c++) {
+      if (c + 1 < cols) h.addEdge(new Set([at(r, c), at(r, c + 1)]));
+      else if (options.periodic && cols > 2) h.addEdge(new Set([at(r, c), at(r, 0)]));
+      if (r + 1 < rows) h.addEdge(new Set([at(r, c), at(r + 1, c)]));
+      else if (options.periodic && rows > 2) h.addEdge(new Set([at(r, c), at(0, c)]));
+    }
+  }
+  return h;
+}
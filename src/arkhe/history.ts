@@ -0,0 +1,145 @@
+import { Hypergraph } from './hypergraph.js';
+import type { HypergraphEvent } from './events.js';
+import type { ArkheNode, ArkheNodeData } from './types.js';
+
+export interface HistoryEntry {
+  tick: number;
+  data: ArkheNodeData;
+  coherence: number;
+}
+
+export interface HistoryOptions {
+  /** Entries kept per node, counting removal markers, oldest dropped first. Defaults to unbounded. */
+  keepLast?: number;
+  /** Keep older entries only from ticks 0, K, 2K, ...; the newest is always kept. Defaults to 1. */
+  keepEvery?: number;
+}
+
+// Marks the tick at whose end a node no longer existed.
+interface Removal {
+  tick: number;
+  removed: true;
+}
+
+type Slot = HistoryEntry | Removal;
+
+function isEntry(slot: Slot | undefined): slot is HistoryEntry {
+  return slot !== undefined && !('removed' in slot);
+}
+
+/**
+ * Records each node's (tick, data, coherence) whenever it changes, from the
+ * graph's event bus. Ticks are advanced by the caller with tick(); several
+ * changes within one tick collapse into the last. History outlives removed
+ * nodes, so earlier ticks stay queryable. Data objects are kept by reference;
+ * Hypergraph methods replace rather than mutate them.
+ */
+export class NodeHistory {
+  private h: Hypergraph;
+  private keepLast: number;
+  private keepEvery: number;
+  private ticks = 0;
+  private tracks: Map<string, Slot[]> = new Map();
+  private unsubscribe: () => void;
+
+  constructor(h: Hypergraph, options: HistoryOptions = {}) {
+    this.h = h;
+    this.keepLast = Math.max(1, options.keepLast ?? Infinity);
+    this.keepEvery = Math.max(1, options.keepEvery ?? 1);
+    for (const node of h.nodes.values()) this.record(node);
+    this.unsubscribe = h.events.subscribe((e) => this.apply(e));
+  }
+
+  public get currentTick(): number {
+    return this.ticks;
+  }
+
+  /** Advance to the next tick. */
+  public tick(): void {
+    this.ticks++;
+  }
+
+  /** Stop recording. */
+  public detach(): void {
+    this.unsubscribe();
+  }
+
+  /** The node's data as of the end of `tick`; undefined before its first retained entry and while removed. */
+  public stateAt(nodeId: string, tick: number): ArkheNodeData | undefined {
+    return this.entryAt(nodeId, tick)?.data;
+  }
+
+  public coherenceAt(nodeId: string, tick: number): number | undefined {
+    return this.entryAt(nodeId, tick)?.coherence;
+  }
+
+  /** (tick, coherence) pairs for every retained entry of the node. */
+  public coherenceTrajectory(nodeId: string): Array<[number, number]> {
+    return this.entries(nodeId).map((e) => [e.tick, e.coherence]);
+  }
+
+  public entries(nodeId: string): HistoryEntry[] {
+    return (this.tracks.get(nodeId) ?? []).filter(isEntry);
+  }
+
+  private entryAt(nodeId: string, tick: number): HistoryEntry | undefined {
+    let found: Slot | undefined;
+    for (const slot of this.tracks.get(nodeId) ?? []) {
+      if (slot.tick > tick) break;
+      found = slot;
+    }
+    return isEntry(found) ? found : undefined;
+  }
+
+  private apply(event: HypergraphEvent): void {
+    switch (event.type) {
+      case 'NodeAdded':
+        this.record(event.node);
+        break;
+      case 'NodeRemoved':
+        this.markRemoved(event.node.id);
+        break;
+      case 'StateChanged':
+      case 'CoherenceUpdated': {
+        const node = this.h.nodes.get(event.nodeId);
+        if (node) this.record(node);
+        break;
+      }
+      case 'BatchApplied':
+      case 'GraphRestored':
+        // Coherence changes are not itemised here, so compare every node.
+        for (const id of this.tracks.keys()) {
+          if (!this.h.nodes.has(id)) this.markRemoved(id);
+        }
+        for (const node of this.h.nodes.values()) this.record(node);
+        break;
+      default:
+        break;
+    }
+  }
+
+  private record(node: ArkheNode): void {
+    let slots = this.tracks.get(node.id);
+    if (!slots) {
+      slots = [];
+      this.tracks.set(node.id, slots);
+    }
+    const last = slots[slots.length - 1];
+    if (isEntry(last) && last.data === node.data && last.coherence === node.coherence) return;
+    this.push(slots, { tick: this.ticks, data: node.data, coherence: node.coherence });
+  }
+
+  private markRemoved(nodeId: string): void {
+    const slots = this.tracks.get(nodeId);
+    if (slots && isEntry(slots[slots.length - 1])) this.push(slots, { tick: this.ticks, removed: true });
+  }
+
+  private push(slots: Slot[], slot: Slot): void {
+    const last = slots[slots.length - 1];
+    // A slot from this tick is superseded; an entry off the keepEvery grid is
+    // thinned once a newer slot arrives. Removal markers are never thinned.
+    if (last && (last.tick === this.ticks || (isEntry(last) && last.tick % this.keepEvery !== 0))) slots.pop();
+    slots.push(slot);
+    if (slots.length > this.keepLast) slots.splice(0, slots.length - this.keepLast);
+  }
+}
@@ -1,50 +1,884 @@
+import { isDeepStrictEqual } from 'util';
 import { ulid } from 'ulid';
-import type { ArkheNode, ArkheHyperedge, ArkheNodeData, HypergraphState } from './types.js';
+import { ArkheError } from './errors.js';
+import { EventBus } from './events.js';
+import type { HypergraphEvent, LifecycleHooks } from './events.js';
+import type { NodeIndex } from './node-index.js';
+import { NodeQuery } from './query.js';
+import type {
+  ArkheEntanglement,
+  ArkheNode,
+  ArkheHyperedge,
+  ArkheNodeData,
+  CoherenceCoupling,
+  CoherenceKernel,
+  GraphMutation,
+  HypergraphState,
+  PropagationOptions,
+  PropagationReport,
+} from './types.js';
+
+/** Handle on a possibly-absent node, in the spirit of Map-entry APIs. */
+export interface NodeEntry {
+  readonly id: string;
+  get(): ArkheNode | undefined;
+  /** Return the node, inserting it with `data` if absent. */
+  orInsert(data?: ArkheNodeData): ArkheNode;
+  /** Return the node, inserting it with `make()` only if absent. */
+  orInsertWith(make: () => ArkheNodeData): ArkheNode;
+  /** Replace the node's data with f(data) if present. */
+  andModify(f: (data: ArkheNodeData) => ArkheNodeData): NodeEntry;
+}
+
+// Live objects are kept next to their copies so rollback can restore into them.
+interface GraphSnapshot {
+  nodes: Array<{ node: ArkheNode; copy: ArkheNode }>;
+  edges: Array<{ edge: ArkheHyperedge; copy: ArkheHyperedge }>;
+  entanglements: ArkheEntanglement[];
+}
+
+function clampUnit(value: number): number {
+  return Math.max(0, Math.min(1, value));
+}
+
+function cloneNode(node: ArkheNode): ArkheNode {
+  return { id: node.id, data: structuredClone(node.data), coherence: node.coherence };
+}
+
+function cloneEdge(edge: ArkheHyperedge): ArkheHyperedge {
+  return {
+    ...edge,
+    nodes: new Set(edge.nodes),
+    sources: edge.sources ? new Set(edge.sources) : undefined,
+  };
+}
+
+/** Restrict an edge to the kept nodes, or return undefined if too little of it survives. */
+function restrictEdge(edge: ArkheHyperedge, keep: Set<string>): ArkheHyperedge | undefined {
+  const nodes = new Set(Array.from(edge.nodes).filter((nid) => keep.has(nid)));
+  if (nodes.size < 2) return undefined;
+  if (edge.sources) {
+    const sources = new Set(Array.from(edge.sources).filter((nid) => keep.has(nid)));
+    if (sources.size === 0 || sources.size === nodes.size) return undefined;
+    return { ...edge, nodes, sources };
+  }
+  return { ...edge, nodes, sources: undefined };
+}
+
+/** Default kernel: weighted average of the node and its neighbors (self weight 1). */
+export const weightedAverageKernel: CoherenceKernel = (node, couplings) => {
+  let sum = node.coherence;
+  let total = 1;
+  for (const { node: neighbor, weight } of couplings) {
+    sum += neighbor.coherence * weight;
+    total += weight;
+  }
+  return sum / total;
+};
 
 export class Hypergraph {
   public nodes: Map<string, ArkheNode> = new Map();
   public edges: ArkheHyperedge[] = [];
+  public events: EventBus = new EventBus();
+  private snapshots: Map<string, GraphSnapshot> = new Map();
+  private links: Map<string, Map<string, number>> = new Map();
+  // Nodes whose coherence inputs changed since the last propagation pass.
+  private dirty: Set<string> = new Set();
+  private allDirty = false;
+  // Events withheld from subscribers while applyBatch runs.
+  private batchEvents?: HypergraphEvent[];
 
   constructor() {}
 
+  private emit(event: HypergraphEvent): void {
+    if (this.batchEvents) this.batchEvents.push(event);
+    else this.events.emit(event);
+  }
+
+  /** Add a node; throws ArkheError DUPLICATE_ID rather than overwriting an existing one. */
   public addNode(nodeId?: string, data: ArkheNodeData = {}): ArkheNode {
     const id = nodeId || ulid();
+    if (this.nodes.has(id)) {
+      throw ArkheError.duplicateId(id);
+    }
     const node: ArkheNode = {
       id,
       data,
       coherence: 1.0,
     };
     this.nodes.set(id, node);
+    this.dirty.add(id);
+    this.emit({ type: 'NodeAdded', node });
+    return node;
+  }
+
+  /**
+   * Remove a node and detach it from its hyperedges.
+   * Hyperedges left with fewer than two nodes are dropped.
+   */
+  public removeNode(nodeId: string): ArkheNode | undefined {
+    const node = this.nodes.get(nodeId);
+    if (!node) return undefined;
+    this.markDirty(...this.neighbors(nodeId), ...(this.links.get(nodeId)?.keys() ?? []));
+    this.nodes.delete(nodeId);
+    this.dirty.delete(nodeId);
+
+    const kept: ArkheHyperedge[] = [];
+    for (const edge of this.edges) {
+      if (edge.nodes.has(nodeId)) {
+        edge.nodes.delete(nodeId);
+        edge.sources?.delete(nodeId);
+        const directedBroken = edge.sources !== undefined
+          && (edge.sources.size === 0 || edge.sources.size === edge.nodes.size);
+        if (edge.nodes.size < 2 || directedBroken) {
+          this.emit({ type: 'EdgeRemoved', edge });
+          continue;
+        }
+      }
+      kept.push(edge);
+    }
+    this.edges = kept;
+    for (const other of this.links.get(nodeId)?.keys() ?? []) {
+      this.links.get(other)?.delete(nodeId);
+    }
+    this.links.delete(nodeId);
+    this.emit({ type: 'NodeRemoved', node });
+    return node;
+  }
+
+  public [Symbol.iterator](): IterableIterator<ArkheNode> {
+    return this.nodes.values();
+  }
+
+  public iter(): IterableIterator<ArkheNode> {
+    return this.nodes.values();
+  }
+
+  public nodeIds(): IterableIterator<string> {
+    return this.nodes.keys();
+  }
+
+  /** Remove and return every node matching the predicate (edges are pruned as in removeNode). */
+  public drainWhere(predicate: (node: ArkheNode) => boolean): ArkheNode[] {
+    const matched = Array.from(this.nodes.values()).filter(predicate);
+    for (const node of matched) {
+      this.removeNode(node.id);
+    }
+    return matched;
+  }
+
+  /** Register lifecycle hooks; any number may be active. Returns an unregister function. */
+  public registerHooks(hooks: LifecycleHooks): () => void {
+    return this.events.subscribe((event) => {
+      switch (event.type) {
+        case 'NodeAdded':
+          hooks.onNodeCreated?.(event.node);
+          break;
+        case 'NodeRemoved':
+          hooks.onNodeDestroyed?.(event.node);
+          break;
+        case 'StateChanged': {
+          const node = this.nodes.get(event.nodeId);
+          if (node) hooks.onStateChanged?.(node, event.previous);
+          break;
+        }
+        case 'BatchApplied':
+        case 'GraphRestored':
+          for (const node of event.created) hooks.onNodeCreated?.(node);
+          for (const node of event.removed) hooks.onNodeDestroyed?.(node);
+          for (const { nodeId, previous } of event.changed) {
+            const node = this.nodes.get(nodeId);
+            if (node) hooks.onStateChanged?.(node, previous);
+          }
+          break;
+        default:
+          break;
+      }
+    });
+  }
+
+  /** Start a chainable node selection, optionally accelerated by a NodeIndex. */
+  public query(index?: NodeIndex): NodeQuery {
+    return new NodeQuery(this, index);
+  }
+
+  public entry(nodeId: string): NodeEntry {
+    const entry: NodeEntry = {
+      id: nodeId,
+      get: () => this.nodes.get(nodeId),
+      orInsert: (data = {}) => this.nodes.get(nodeId) ?? this.addNode(nodeId, data),
+      orInsertWith: (make) => this.nodes.get(nodeId) ?? this.addNode(nodeId, make()),
+      andModify: (f) => {
+        const node = this.nodes.get(nodeId);
+        if (node) this.setNodeData(nodeId, f(node.data));
+        return entry;
+      },
+    };
+    return entry;
+  }
+
+  /** Merge a patch into a node's data and notify subscribers. */
+  public updateNode(nodeId: string, patch: ArkheNodeData): ArkheNode {
+    const node = this.nodes.get(nodeId);
+    if (!node) {
+      throw ArkheError.nodeNotFound(nodeId);
+    }
+    const previous = node.data;
+    node.data = { ...node.data, ...patch };
+    this.dirty.add(nodeId);
+    this.emit({ type: 'StateChanged', nodeId, previous, data: node.data });
+    return node;
+  }
+
+  /** Replace a node's data wholesale and notify subscribers. */
+  public setNodeData(nodeId: string, data: ArkheNodeData): ArkheNode {
+    const node = this.nodes.get(nodeId);
+    if (!node) {
+      throw ArkheError.nodeNotFound(nodeId);
+    }
+    const previous = node.data;
+    node.data = data;
+    this.dirty.add(nodeId);
+    this.emit({ type: 'StateChanged', nodeId, previous, data });
     return node;
   }
 
-  public addEdge(nodeIds: Set<string>, weight: number = 1.0): ArkheHyperedge {
+  /**
+   * Set a node's coherence, clamped to [0, 1], and notify subscribers if it
+   * changed. Unless `shiftEntangled` is false, entangled nodes shift by
+   * strength × the change (one hop, also clamped).
+   */
+  public setCoherence(nodeId: string, coherence: number, shiftEntangled: boolean = true): void {
+    const clamped = clampUnit(coherence);
+    const previous = this.assignCoherence(nodeId, clamped);
+    const delta = clamped - previous;
+    if (delta === 0 || !shiftEntangled) return;
+    for (const [other, strength] of this.links.get(nodeId) ?? []) {
+      const linked = this.nodes.get(other)!;
+      this.assignCoherence(other, clampUnit(linked.coherence + strength * delta));
+    }
+  }
+
+  /**
+   * Set several nodes' coherence at once. Entangled partners then shift by
+   * strength × each change, all measured from the values before the call (as
+   * in propagateCoherence), so the order of `values` does not matter. Results
+   * are clamped to [0, 1].
+   */
+  public setCoherences(values: Map<string, number>): void {
+    for (const id of values.keys()) {
+      if (!this.nodes.has(id)) throw ArkheError.nodeNotFound(id);
+    }
+    const next = new Map<string, number>();
+    for (const [id, value] of values) next.set(id, clampUnit(value));
+    const raw = new Map(next);
+    for (const [a, value] of raw) {
+      const delta = value - this.nodes.get(a)!.coherence;
+      if (delta === 0) continue;
+      for (const [b, strength] of this.links.get(a) ?? []) {
+        next.set(b, (next.get(b) ?? this.nodes.get(b)!.coherence) + strength * delta);
+      }
+    }
+    for (const [id, value] of next) {
+      this.assignCoherence(id, clampUnit(value));
+    }
+  }
+
+  private assignCoherence(nodeId: string, coherence: number): number {
+    const node = this.nodes.get(nodeId);
+    if (!node) {
+      throw ArkheError.nodeNotFound(nodeId);
+    }
+    const previous = node.coherence;
+    node.coherence = coherence;
+    if (previous !== coherence) {
+      this.dirty.add(nodeId);
+      this.emit({ type: 'CoherenceUpdated', nodeId, previous, coherence });
+    }
+    return previous;
+  }
+
+  /** Correlate two nodes' coherence with a coefficient in [-1, 1]; replaces any existing link. */
+  public entangle(a: string, b: string, strength: number): void {
+    for (const nid of [a, b]) {
+      if (!this.nodes.has(nid)) throw ArkheError.nodeNotFound(nid);
+    }
+    if (a === b) {
+      throw new ArkheError('INVALID_ENTANGLEMENT', `Node ${a} cannot be entangled with itself`, a);
+    }
+    if (strength < -1 || strength > 1) {
+      throw new ArkheError('INVALID_ENTANGLEMENT', `Entanglement strength ${strength} outside [-1, 1]`);
+    }
+    for (const [x, y] of [[a, b], [b, a]]) {
+      let peers = this.links.get(x);
+      if (!peers) {
+        peers = new Map();
+        this.links.set(x, peers);
+      }
+      peers.set(y, strength);
+    }
+    this.markDirty(a, b);
+  }
+
+  public disentangle(a: string, b: string): boolean {
+    const removed = this.links.get(a)?.delete(b) ?? false;
+    this.links.get(b)?.delete(a);
+    if (removed) this.markDirty(a, b);
+    return removed;
+  }
+
+  /** Entanglement links touching a node. */
+  public entanglementsOf(nodeId: string): ArkheEntanglement[] {
+    return Array.from(this.links.get(nodeId) ?? [], ([b, strength]) => ({ a: nodeId, b, strength }));
+  }
+
+  /** Every entanglement link, each pair listed once. */
+  public entanglements(): ArkheEntanglement[] {
+    const result: ArkheEntanglement[] = [];
+    for (const [a, peers] of this.links) {
+      for (const [b, strength] of peers) {
+        if (a < b) result.push({ a, b, strength });
+      }
+    }
+    return result;
+  }
+
+  /**
+   * Add an undirected hyperedge over at least two nodes (INVALID_EDGE otherwise).
+   * `id` defaults to a fresh ULID; a taken ID throws DUPLICATE_ID.
+   */
+  public addEdge(nodeIds: Set<string>, weight: number = 1.0, label?: string, id?: string): ArkheHyperedge {
+    if (nodeIds.size < 2) {
+      throw new ArkheError('INVALID_EDGE', `Hyperedge needs at least two nodes, got ${nodeIds.size}`);
+    }
     for (const nid of nodeIds) {
       if (!this.nodes.has(nid)) {
-        throw new Error(`Node ${nid} does not exist`);
+        throw ArkheError.nodeNotFound(nid);
       }
     }
-    const edge: ArkheHyperedge = {
-      id: ulid(),
+    return this.pushEdge({
+      id: id ?? ulid(),
       nodes: nodeIds,
       weight,
-    };
+      label,
+    }, id !== undefined);
+  }
+
+  /** Add a directed hyperedge whose sources influence its targets but not the reverse. `id` as for addEdge. */
+  public addDirectedEdge(
+    sources: Set<string>,
+    targets: Set<string>,
+    weight: number = 1.0,
+    label?: string,
+    id?: string,
+  ): ArkheHyperedge {
+    if (sources.size === 0 || targets.size === 0) {
+      throw new ArkheError('INVALID_EDGE', 'Directed hyperedge needs at least one source and one target');
+    }
+    for (const nid of sources) {
+      if (targets.has(nid)) {
+        throw new ArkheError('INVALID_EDGE', `Node ${nid} cannot be both source and target`, nid);
+      }
+    }
+    const members = new Set([...sources, ...targets]);
+    for (const nid of members) {
+      if (!this.nodes.has(nid)) {
+        throw ArkheError.nodeNotFound(nid);
+      }
+    }
+    return this.pushEdge({
+      id: id ?? ulid(),
+      nodes: members,
+      weight,
+      label,
+      sources: new Set(sources),
+    }, id !== undefined);
+  }
+
+  /**
+   * Add an edge from its stored layout: all members, plus the sources if it is
+   * directed (the remaining members become targets). Sources need not be
+   * repeated in `nodes`. Used to rebuild edges from dumps and other graphs.
+   */
+  public insertEdge(
+    nodes: Iterable<string>,
+    sources?: Iterable<string>,
+    weight: number = 1.0,
+    label?: string,
+    id?: string,
+  ): ArkheHyperedge {
+    if (!sources) {
+      return this.addEdge(new Set(nodes), weight, label, id);
+    }
+    const from = new Set(sources);
+    const targets = new Set(Array.from(nodes).filter((nid) => !from.has(nid)));
+    return this.addDirectedEdge(from, targets, weight, label, id);
+  }
+
+  private pushEdge(edge: ArkheHyperedge, explicitId: boolean): ArkheHyperedge {
+    // Generated ULIDs cannot collide, so only caller-supplied IDs are checked.
+    if (explicitId && this.edges.some((e) => e.id === edge.id)) {
+      throw new ArkheError('DUPLICATE_ID', `Edge ${edge.id} already exists`);
+    }
     this.edges.push(edge);
+    this.markDirty(...edge.nodes);
+    this.emit({ type: 'EdgeAdded', edge });
+    return edge;
+  }
+
+  /** Remove a hyperedge by ID. */
+  public removeEdge(edgeId: string): ArkheHyperedge | undefined {
+    const i = this.edges.findIndex((e) => e.id === edgeId);
+    if (i < 0) return undefined;
+    const [edge] = this.edges.splice(i, 1);
+    this.markDirty(...edge.nodes);
+    this.emit({ type: 'EdgeRemoved', edge });
     return edge;
   }
 
+  /**
+   * Apply a list of mutations as one unit. If any mutation fails the applied
+   * ones are undone, subscribers get GraphRestored, and the error is rethrown.
+   * On success subscribers receive a single BatchApplied event, carrying the
+   * net node, edge and coherence changes, instead of one event per mutation.
+   */
+  public applyBatch(mutations: GraphMutation[]): void {
+    const undo: Array<() => void> = [];
+    const withheld: HypergraphEvent[] = [];
+    this.batchEvents = withheld;
+    try {
+      for (const m of mutations) {
+        this.applyMutation(m, undo);
+      }
+    } catch (err) {
+      this.batchEvents = undefined;
+      for (let i = undo.length - 1; i >= 0; i--) undo[i]();
+      this.allDirty = true;
+      this.emit({ type: 'GraphRestored', created: [], removed: [], changed: [] });
+      throw err;
+    }
+    this.batchEvents = undefined;
+
+    const created = new Map<string, ArkheNode>();
+    const removed = new Map<string, ArkheNode>();
+    const changed = new Map<string, ArkheNodeData>();
+    const addedEdges = new Set<ArkheHyperedge>();
+    const removedEdges = new Set<ArkheHyperedge>();
+    const coherence = new Map<string, number>();
+    for (const event of withheld) {
+      if (event.type === 'EdgeAdded') {
+        addedEdges.add(event.edge);
+      } else if (event.type === 'EdgeRemoved') {
+        if (!addedEdges.delete(event.edge)) removedEdges.add(event.edge);
+      } else if (event.type === 'CoherenceUpdated') {
+        if (!coherence.has(event.nodeId)) coherence.set(event.nodeId, event.previous);
+      } else if (event.type === 'NodeAdded') {
+        created.set(event.node.id, event.node);
+      } else if (event.type === 'NodeRemoved') {
+        if (!created.delete(event.node.id)) removed.set(event.node.id, event.node);
+        changed.delete(event.node.id);
+      } else if (event.type === 'StateChanged') {
+        if (!created.has(event.nodeId) && !changed.has(event.nodeId)) changed.set(event.nodeId, event.previous);
+      }
+    }
+    const moves: Array<{ nodeId: string; previous: number; coherence: number }> = [];
+    for (const [nodeId, previous] of coherence) {
+      const node = this.nodes.get(nodeId);
+      if (node && !created.has(nodeId) && node.coherence !== previous) {
+        moves.push({ nodeId, previous, coherence: node.coherence });
+      }
+    }
+    this.emit({
+      type: 'BatchApplied',
+      mutations: mutations.length,
+      created: Array.from(created.values()),
+      removed: Array.from(removed.values()),
+      changed: Array.from(changed, ([nodeId, previous]) => ({ nodeId, previous })),
+      addedEdges: Array.from(addedEdges),
+      removedEdges: Array.from(removedEdges),
+      coherence: moves,
+    });
+  }
+
+  /** Apply one batch step, pushing a closure that reverts it onto `undo`. */
+  private applyMutation(m: GraphMutation, undo: Array<() => void>): void {
+    switch (m.op) {
+      case 'addNode': {
+        const node = this.addNode(m.id, m.data);
+        undo.push(() => this.nodes.delete(node.id));
+        break;
+      }
+      case 'removeNode': {
+        const node = this.nodes.get(m.id);
+        if (!node) throw ArkheError.nodeNotFound(m.id);
+        const edges = this.edges;
+        const incident = this.edgesOf(m.id).map((e) => ({ edge: e, restored: cloneEdge(e) }));
+        const peers = new Map(this.links.get(m.id) ?? []);
+        this.removeNode(m.id);
+        undo.push(() => {
+          this.nodes.set(node.id, node);
+          for (const { edge, restored } of incident) {
+            edge.nodes = restored.nodes;
+            edge.sources = restored.sources;
+          }
+          this.edges = edges;
+          if (peers.size > 0) this.links.set(node.id, peers);
+          for (const [other, strength] of peers) this.links.get(other)?.set(node.id, strength);
+        });
+        break;
+      }
+      case 'setState': {
+        const node = this.nodes.get(m.id);
+        if (!node) throw ArkheError.nodeNotFound(m.id);
+        const previous = node.data;
+        this.setNodeData(m.id, m.data);
+        undo.push(() => {
+          node.data = previous;
+        });
+        break;
+      }
+      case 'addEdge': {
+        const edge = this.addEdge(new Set(m.nodes), m.weight, m.label);
+        undo.push(() => this.edges.splice(this.edges.indexOf(edge), 1));
+        break;
+      }
+      case 'removeEdge': {
+        const i = this.edges.findIndex((e) => e.id === m.edgeId);
+        if (i < 0) throw new ArkheError('EDGE_NOT_FOUND', `Edge ${m.edgeId} does not exist`);
+        const edge = this.removeEdge(m.edgeId)!;
+        undo.push(() => this.edges.splice(i, 0, edge));
+        break;
+      }
+      case 'adjustCoherence': {
+        const node = this.nodes.get(m.id);
+        if (!node) throw ArkheError.nodeNotFound(m.id);
+        // setCoherence also shifts entangled partners.
+        const touched = [node, ...Array.from(this.links.get(m.id)?.keys() ?? [], (id) => this.nodes.get(id)!)]
+          .map((n) => ({ n, coherence: n.coherence }));
+        this.setCoherence(m.id, node.coherence + m.delta);
+        undo.push(() => {
+          for (const { n, coherence } of touched) n.coherence = coherence;
+        });
+        break;
+      }
+    }
+  }
+
+  /** All hyperedges incident to the given node. */
+  public edgesOf(nodeId: string): ArkheHyperedge[] {
+    return this.edges.filter((e) => e.nodes.has(nodeId));
+  }
+
+  /** Nodes sharing at least one hyperedge with the given node. */
+  public neighbors(nodeId: string): Set<string> {
+    const result = new Set<string>();
+    for (const edge of this.edgesOf(nodeId)) {
+      for (const nid of edge.nodes) {
+        if (nid !== nodeId) result.add(nid);
+      }
+    }
+    return result;
+  }
+
+  /** Number of hyperedges incident to the given node. */
+  public degree(nodeId: string): number {
+    return this.edgesOf(nodeId).length;
+  }
+
+  /** Hyperedges whose node set contains every one of the given nodes. */
+  public edgesContaining(nodeIds: Iterable<string>): ArkheHyperedge[] {
+    const required = Array.from(nodeIds);
+    return this.edges.filter((e) => required.every((nid) => e.nodes.has(nid)));
+  }
+
   public bootstrapStep(): void {
-    /** Single bootstrap iteration: update node coherence based on incident edges. */
+    /** Single bootstrap iteration: update node coherence based on incident edges, clamped to [0, 1]. */
     for (const node of this.nodes.values()) {
       const incidentWeights = this.edges
         .filter((e) => e.nodes.has(node.id))
         .map((e) => e.weight);
 
+      const previous = node.coherence;
       if (incidentWeights.length > 0) {
-        node.coherence = incidentWeights.reduce((a, b) => a + b, 0) / incidentWeights.length;
+        node.coherence = clampUnit(incidentWeights.reduce((a, b) => a + b, 0) / incidentWeights.length);
       } else {
         node.coherence = 0.0;
       }
+      if (node.coherence !== previous) {
+        this.dirty.add(node.id);
+        this.emit({ type: 'CoherenceUpdated', nodeId: node.id, previous, coherence: node.coherence });
+      }
+    }
+  }
+
+  /**
+   * Nodes that influence the given node, with the summed weight of every
+   * hyperedge they share. Directed edges only couple targets to their sources.
+   */
+  public couplings(nodeId: string): CoherenceCoupling[] {
+    const weights = new Map<string, number>();
+    for (const edge of this.edgesOf(nodeId)) {
+      if (edge.sources?.has(nodeId)) continue;
+      for (const nid of edge.sources ?? edge.nodes) {
+        if (nid === nodeId) continue;
+        weights.set(nid, (weights.get(nid) ?? 0) + edge.weight);
+      }
+    }
+    const result: CoherenceCoupling[] = [];
+    for (const [nid, weight] of weights) {
+      const node = this.nodes.get(nid);
+      if (node) result.push({ node, weight });
+    }
+    return result;
+  }
+
+  /**
+   * Iteratively update every node's coherence from its hyperedge neighbors
+   * until the largest change falls below the tolerance or maxIterations is hit.
+   * With `incremental`, only the neighborhood of nodes touched since the last
+   * pass is recomputed; a plain call is always a full pass. Kernel results
+   * are clamped to [0, 1].
+   */
+  public propagateCoherence(options: PropagationOptions = {}): PropagationReport {
+    const maxIterations = options.maxIterations ?? 100;
+    const tolerance = options.tolerance ?? 1e-6;
+    const kernel = options.kernel ?? weightedAverageKernel;
+
+    // Incremental passes start from the dirty neighborhood and grow the region
+    // only where a node moves by at least the tolerance.
+    const region = options.incremental && !this.allDirty ? this.dirtyRegion() : undefined;
+    const initial = new Map<string, number>();
+    const include = (id: string) => {
+      if (!initial.has(id)) initial.set(id, this.nodes.get(id)!.coherence);
+      region?.add(id);
+    };
+    for (const id of region ?? this.nodes.keys()) include(id);
+
+    let iterations = 0;
+    let converged = region !== undefined && region.size === 0;
+    while (!converged && iterations < maxIterations) {
+      iterations++;
+      // Jacobi-style update: every node sees the previous iteration's values.
+      const next = new Map<string, number>();
+      for (const id of region ?? this.nodes.keys()) {
+        next.set(id, clampUnit(kernel(this.nodes.get(id)!, this.couplings(id))));
+      }
+      if (this.links.size > 0) {
+        // Entangled partners follow each other's kernel change.
+        const raw = new Map(next);
+        for (const [a, peers] of this.links) {
+          if (!raw.has(a)) continue;
+          const delta = raw.get(a)! - this.nodes.get(a)!.coherence;
+          if (delta === 0) continue;
+          for (const [b, strength] of peers) {
+            include(b);
+            next.set(b, (next.get(b) ?? this.nodes.get(b)!.coherence) + strength * delta);
+          }
+        }
+        for (const [id, value] of next) {
+          if (value !== raw.get(id)) next.set(id, clampUnit(value));
+        }
+      }
+      let maxDelta = 0;
+      for (const [id, value] of next) {
+        const node = this.nodes.get(id)!;
+        const change = Math.abs(value - node.coherence);
+        maxDelta = Math.max(maxDelta, change);
+        node.coherence = value;
+        if (region && change >= tolerance) {
+          for (const nid of this.neighbors(id)) include(nid);
+        }
+      }
+      if (maxDelta < tolerance) {
+        converged = true;
+      }
+    }
+
+    this.dirty.clear();
+    this.allDirty = false;
+    const deltas: Record<string, number> = {};
+    for (const [id, previous] of initial) {
+      const node = this.nodes.get(id)!;
+      deltas[id] = node.coherence - previous;
+      if (node.coherence !== previous) {
+        this.emit({ type: 'CoherenceUpdated', nodeId: id, previous, coherence: node.coherence });
+      }
+    }
+    return {
+      iterations,
+      converged,
+      globalCoherence: this.totalCoherence(),
+      deltas,
+    };
+  }
+
+  /** Flag nodes for the next incremental pass, e.g. after writing node fields directly. */
+  public markDirty(...nodeIds: string[]): void {
+    for (const id of nodeIds) {
+      if (this.nodes.has(id)) this.dirty.add(id);
+    }
+  }
+
+  /** Nodes an incremental propagation pass would start from. */
+  public dirtyNodes(): Set<string> {
+    return this.allDirty ? new Set(this.nodes.keys()) : new Set(this.dirty);
+  }
+
+  private dirtyRegion(): Set<string> {
+    const region = new Set<string>();
+    for (const id of this.dirty) {
+      if (!this.nodes.has(id)) continue;
+      region.add(id);
+      for (const nid of this.neighbors(id)) region.add(nid);
+    }
+    return region;
+  }
+
+
+  /**
+   * Build a new graph from the given nodes, keeping IDs and the hyperedges
+   * induced on them. Callers own the node objects they pass in. Nothing has
+   * been propagated in the new graph yet, so all of it starts dirty.
+   */
+  private induced(nodes: ArkheNode[]): Hypergraph {
+    const h = new Hypergraph();
+    for (const node of nodes) {
+      h.nodes.set(node.id, node);
+    }
+    const keep = new Set(h.nodes.keys());
+    for (const edge of this.edges) {
+      const restricted = restrictEdge(edge, keep);
+      if (restricted) h.edges.push(restricted);
+    }
+    for (const { a, b, strength } of this.entanglements()) {
+      if (keep.has(a) && keep.has(b)) h.entangle(a, b, strength);
+    }
+    h.allDirty = true;
+    return h;
+  }
+
+  /** Map every node's data into a new graph with the same IDs, edges and coherence. */
+  public map(f: (node: ArkheNode) => ArkheNodeData): Hypergraph {
+    return this.induced(
+      Array.from(this.nodes.values(), (n) => ({ id: n.id, data: f(n), coherence: n.coherence })),
+    );
+  }
+
+  /** Copy the graph with every node's coherence replaced by f(node), clamped to [0, 1]. */
+  public mapCoherence(f: (node: ArkheNode) => number): Hypergraph {
+    return this.induced(
+      Array.from(this.nodes.values(), (n) => ({ id: n.id, data: structuredClone(n.data), coherence: clampUnit(f(n)) })),
+    );
+  }
+
+  /**
+   * Map nodes into a new graph, dropping those for which f returns undefined.
+   * Edges keep only surviving members and vanish when fewer than two remain.
+   */
+  public filterMapNodes(f: (node: ArkheNode) => ArkheNodeData | undefined): Hypergraph {
+    const kept: ArkheNode[] = [];
+    for (const n of this.nodes.values()) {
+      const data = f(n);
+      if (data !== undefined) kept.push({ id: n.id, data, coherence: n.coherence });
+    }
+    return this.induced(kept);
+  }
+
+  /** Induced sub-hypergraph on the given nodes; edges are restricted to the selection. */
+  public subgraph(nodeIds: Iterable<string>): Hypergraph {
+    const selected: ArkheNode[] = [];
+    for (const nid of nodeIds) {
+      const node = this.nodes.get(nid);
+      if (!node) {
+        throw ArkheError.nodeNotFound(nid);
+      }
+      selected.push(cloneNode(node));
+    }
+    return this.induced(selected);
+  }
+
+  /** Induced sub-hypergraph on the nodes matching a predicate. */
+  public subgraphWhere(predicate: (node: ArkheNode) => boolean): Hypergraph {
+    return this.induced(Array.from(this.nodes.values()).filter(predicate).map(cloneNode));
+  }
+
+  /** Capture nodes, edges and coherence so they can be restored with rollback(). */
+  public snapshot(): string {
+    const id = ulid();
+    this.snapshots.set(id, {
+      nodes: Array.from(this.nodes.values(), (node) => ({ node, copy: cloneNode(node) })),
+      edges: this.edges.map((edge) => ({ edge, copy: cloneEdge(edge) })),
+      entanglements: this.entanglements(),
+    });
+    return id;
+  }
+
+  /**
+   * Restore the graph to a snapshot. Nodes and edges that existed when it was
+   * taken are restored in place, so references held since then stay valid.
+   * The snapshot stays available for later rollbacks.
+   */
+  public rollback(snapshotId: string): void {
+    const snap = this.snapshots.get(snapshotId);
+    if (!snap) {
+      throw new ArkheError('SNAPSHOT_NOT_FOUND', `Snapshot ${snapshotId} does not exist`);
+    }
+    const before = this.nodes;
+    const changed: Array<{ nodeId: string; previous: ArkheNodeData }> = [];
+    this.nodes = new Map(snap.nodes.map(({ node, copy }) => {
+      const current = before.get(node.id);
+      if (current && !isDeepStrictEqual(current.data, copy.data)) {
+        changed.push({ nodeId: node.id, previous: current.data });
+      }
+      if (!isDeepStrictEqual(node.data, copy.data)) node.data = structuredClone(copy.data);
+      node.coherence = copy.coherence;
+      return [node.id, node];
+    }));
+    this.edges = snap.edges.map(({ edge, copy }) => {
+      edge.nodes = new Set(copy.nodes);
+      edge.sources = copy.sources && new Set(copy.sources);
+      edge.weight = copy.weight;
+      edge.label = copy.label;
+      return edge;
+    });
+    this.links = new Map();
+    for (const { a, b, strength } of snap.entanglements) {
+      this.entangle(a, b, strength);
+    }
+    this.allDirty = true;
+    this.emit({
+      type: 'GraphRestored',
+      snapshotId,
+      created: Array.from(this.nodes.values()).filter((n) => !before.has(n.id)),
+      removed: Array.from(before.values()).filter((n) => !this.nodes.has(n.id)),
+      changed,
+    });
+  }
+
+  public discardSnapshot(snapshotId: string): boolean {
+    return this.snapshots.delete(snapshotId);
+  }
+
+  /**
+   * Run a group of mutations atomically. If `fn` throws or calls `abort()`,
+   * the graph is rolled back to its state before the transaction; an abort
+   * returns undefined while other errors are rethrown after rollback.
+   * Subscribers see mutations as they happen, then GraphRestored on rollback.
+   */
+  public transaction<T>(fn: (h: Hypergraph, abort: () => never) => T): T | undefined {
+    const aborted = Symbol('aborted');
+    const abort = () => {
+      throw aborted;
+    };
+    const snap = this.snapshot();
+    try {
+      return fn(this, abort);
+    } catch (err) {
+      this.rollback(snap);
+      if (err === aborted) return undefined;
+      throw err;
+    } finally {
+      this.discardSnapshot(snap);
     }
   }
 
@@ -59,15 +893,22 @@ export class Hypergraph {
 
   public toJSON(): HypergraphState {
     const nodes: Record<string, ArkheNodeData> = {};
+    const coherence: Record<string, number> = {};
     for (const [id, node] of this.nodes) {
       nodes[id] = node.data;
+      coherence[id] = node.coherence;
     }
     return {
       nodes,
       edges: this.edges.map((e) => ({
+        id: e.id,
         nodes: Array.from(e.nodes),
         weight: e.weight,
+        ...(e.label !== undefined ? { label: e.label } : {}),
+        ...(e.sources ? { sources: Array.from(e.sources) } : {}),
       })),
+      coherence,
+      entanglements: this.entanglements(),
     };
   }
 
@@ -75,9 +916,15 @@ export class Hypergraph {
     const h = new Hypergraph();
     for (const [id, data] of Object.entries(state.nodes)) {
       h.addNode(id, data);
+      if (state.coherence?.[id] !== undefined) {
+        h.setCoherence(id, state.coherence[id]);
+      }
     }
     for (const e of state.edges) {
-      h.addEdge(new Set(e.nodes), e.weight);
+      h.insertEdge(e.nodes, e.sources, e.weight, e.label, e.id);
+    }
+    for (const { a, b, strength } of state.entanglements ?? []) {
+      h.entangle(a, b, strength);
     }
     return h;
   }
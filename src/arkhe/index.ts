@@ -1,9 +1,34 @@
 export * from './types.js';
+export * from './errors.js';
 export * from './constants.js';
 export * from './hypergraph.js';
+export * from './builder.js';
+export * from './constructions.js';
+export * from './merge.js';
+export * from './diff.js';
+export * from './consensus.js';
+export * from './digest.js';
+export * from './generators.js';
+export * from './invariants.js';
+export * from './events.js';
+export * from './node-index.js';
+export * from './query.js';
+export * from './reaper.js';
+export * from './export.js';
+export * from './interchange.js';
+export * from './envelope.js';
+export * from './storage.js';
 export * from './cognitive-core.js';
 export * from './bootstrap.js';
 export * from './coherence.js';
+export * from './metrics.js';
+export * from './observables.js';
+export * from './history.js';
+export * from './evolution.js';
+export * from './journal.js';
+export * from './topology.js';
+export * from './partition.js';
+export * from './motifs.js';
 export * from './constitution.js';
 export * from './symbiosis.js';
 export * from './simulations.js';
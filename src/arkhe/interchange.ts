@@ -0,0 +1,220 @@
+import { ArkheError } from './errors.js';
+import { Hypergraph } from './hypergraph.js';
+import type { ArkheNodeData } from './types.js';
+
+/** Translates a node's imported attributes into its data. */
+export type AttributeMapper = (nodeId: string, attributes: Record<string, unknown>) => ArkheNodeData;
+
+export interface ImportOptions {
+  /** Defaults to using the attributes as node data. */
+  mapNode?: AttributeMapper;
+  /** Numeric node attribute applied as coherence and left out of the data. Defaults to 'coherence'. */
+  coherenceKey?: string;
+  /** Numeric edge attribute used as weight. Defaults to 'weight'. */
+  weightKey?: string;
+  /**
+   * Called for each edge with no hyperedge form, which is skipped: self-loops
+   * and other edges over a single node, and directed edges without a target.
+   */
+  onSkippedEdge?: (edge: ImportedEdge) => void;
+}
+
+/** An edge as read from the document, before it becomes a hyperedge. */
+export interface ImportedEdge {
+  nodes: string[];
+  sources?: string[];
+  attributes: Record<string, unknown>;
+  label?: string;
+}
+
+function invalid(message: string): ArkheError {
+  return new ArkheError('INVALID_FORMAT', message);
+}
+
+function build(
+  nodes: Array<{ id: string; attributes: Record<string, unknown> }>,
+  edges: ImportedEdge[],
+  options: ImportOptions,
+): Hypergraph {
+  const mapNode = options.mapNode ?? ((_id: string, attributes: Record<string, unknown>) => attributes);
+  const coherenceKey = options.coherenceKey ?? 'coherence';
+  const weightKey = options.weightKey ?? 'weight';
+
+  const h = new Hypergraph();
+  for (const { id, attributes } of nodes) {
+    const { [coherenceKey]: coherence, ...rest } = attributes;
+    h.addNode(id, mapNode(id, rest));
+    if (typeof coherence === 'number') h.setCoherence(id, coherence);
+  }
+  for (const edge of edges) {
+    const members = new Set([...edge.nodes, ...(edge.sources ?? [])]);
+    if (members.size < 2 || (edge.sources && new Set(edge.sources).size === members.size)) {
+      options.onSkippedEdge?.(edge);
+      continue;
+    }
+    const raw = edge.attributes[weightKey];
+    const weight = typeof raw === 'number' ? raw : 1.0;
+    const label = edge.label ?? (typeof edge.attributes.label === 'string' ? edge.attributes.label : undefined);
+    h.insertEdge(edge.nodes, edge.sources, weight, label);
+  }
+  return h;
+}
+
+function asRecord(value: unknown): Record<string, unknown> {
+  return value !== null && typeof value === 'object' ? (value as Record<string, unknown>) : {};
+}
+
+/**
+ * Build a hypergraph from a JSON Graph Format document (v1 node arrays or v2
+ * node maps). Plain edges become two-node hyperedges, directed when the edge
+ * or graph says so; v2 `hyperedges` are imported as-is.
+ */
+export function fromJsonGraph(value: unknown, options: ImportOptions = {}): Hypergraph {
+  const graph = asRecord(asRecord(value).graph);
+  if (!graph.nodes) {
+    throw invalid('JSON graph has no graph.nodes');
+  }
+  const directedGraph = graph.directed === true;
+
+  const nodes = Array.isArray(graph.nodes)
+    ? graph.nodes.map((n) => asRecord(n)).map((n) => ({ id: String(n.id), record: n }))
+    : Object.entries(asRecord(graph.nodes)).map(([id, n]) => ({ id, record: asRecord(n) }));
+
+  const edges: ImportedEdge[] = [];
+  for (const e of Array.isArray(graph.edges) ? graph.edges.map(asRecord) : []) {
+    if (e.source === undefined || e.target === undefined) {
+      throw invalid('JSON graph edge needs a source and a target');
+    }
+    const source = String(e.source);
+    const directed = typeof e.directed === 'boolean' ? e.directed : directedGraph;
+    edges.push({
+      nodes: [source, String(e.target)],
+      sources: directed ? [source] : undefined,
+      attributes: asRecord(e.metadata),
+      label: typeof e.label === 'string' ? e.label : undefined,
+    });
+  }
+  for (const e of Array.isArray(graph.hyperedges) ? graph.hyperedges.map(asRecord) : []) {
+    const label = typeof e.label === 'string' ? e.label : undefined;
+    if (Array.isArray(e.nodes)) {
+      edges.push({ nodes: e.nodes.map(String), attributes: asRecord(e.metadata), label });
+    } else if (Array.isArray(e.source) && Array.isArray(e.target)) {
+      const sources = e.source.map(String);
+      edges.push({ nodes: [...sources, ...e.target.map(String)], sources, attributes: asRecord(e.metadata), label });
+    } else {
+      throw invalid('JSON graph hyperedge needs nodes, or source and target arrays');
+    }
+  }
+
+  return build(
+    nodes.map(({ id, record }) => ({
+      id,
+      attributes: {
+        ...asRecord(record.metadata),
+        ...(typeof record.label === 'string' ? { label: record.label } : {}),
+      },
+    })),
+    edges,
+    options,
+  );
+}
+
+const ENTITIES: Record<string, string> = { lt: '<', gt: '>', amp: '&', quot: '"', apos: "'" };
+
+function decodeXml(text: string): string {
+  return text.replace(/&(#x[0-9a-f]+|#\d+|\w+);/gi, (match, name: string) => {
+    if (name[0] === '#') {
+      return String.fromCodePoint(name[1] === 'x' || name[1] === 'X' ? parseInt(name.slice(2), 16) : Number(name.slice(1)));
+    }
+    return ENTITIES[name] ?? match;
+  });
+}
+
+function xmlAttributes(tag: string): Record<string, string> {
+  const attrs: Record<string, string> = {};
+  for (const m of tag.matchAll(/([\w.:-]+)\s*=\s*("([^"]*)"|'([^']*)')/g)) {
+    attrs[m[1]] = decodeXml(m[3] ?? m[4]);
+  }
+  return attrs;
+}
+
+/** Elements named `tag`: opening-tag attributes plus inner XML (empty for self-closing tags). */
+function xmlElements(xml: string, tag: string): Array<{ attrs: Record<string, string>; body: string }> {
+  const pattern = new RegExp(`<${tag}\\b([^>]*?)(/>|>([\\s\\S]*?)</${tag}>)`, 'g');
+  return Array.from(xml.matchAll(pattern), (m) => ({ attrs: xmlAttributes(m[1]), body: m[3] ?? '' }));
+}
+
+interface GraphMLKey {
+  name: string;
+  type: string;
+}
+
+function keyValue(key: GraphMLKey | undefined, text: string): unknown {
+  switch (key?.type) {
+    case 'int':
+    case 'long':
+    case 'float':
+    case 'double':
+      return Number(text);
+    case 'boolean':
+      return text.trim() === 'true';
+    default:
+      return text;
+  }
+}
+
+function dataOf(body: string, keys: Map<string, GraphMLKey>): Record<string, unknown> {
+  const attributes: Record<string, unknown> = {};
+  for (const { attrs, body: text } of xmlElements(body, 'data')) {
+    const key = keys.get(attrs.key);
+    attributes[key?.name ?? attrs.key] = keyValue(key, decodeXml(text));
+  }
+  return attributes;
+}
+
+/**
+ * Build a hypergraph from a GraphML document. Supports `<key>` declarations
+ * with typed `<data>` values, directed and undirected `<edge>`s, and
+ * `<hyperedge>`s with `<endpoint>`s (type="out" marks a source). Nested graphs
+ * and ports are not supported.
+ */
+export function fromGraphML(xml: string, options: ImportOptions = {}): Hypergraph {
+  const graphs = xmlElements(xml, 'graph');
+  if (graphs.length === 0) {
+    throw invalid('GraphML document has no <graph> element');
+  }
+  const { attrs: graphAttrs, body } = graphs[0];
+  const keys = new Map<string, GraphMLKey>();
+  for (const { attrs } of xmlElements(xml, 'key')) {
+    keys.set(attrs.id, { name: attrs['attr.name'] ?? attrs.id, type: attrs['attr.type'] ?? 'string' });
+  }
+  const directedGraph = graphAttrs.edgedefault === 'directed';
+
+  const nodes = xmlElements(body, 'node').map(({ attrs, body: inner }) => {
+    if (!attrs.id) throw invalid('GraphML node without an id');
+    return { id: attrs.id, attributes: dataOf(inner, keys) };
+  });
+
+  const edges: ImportedEdge[] = xmlElements(body, 'edge').map(({ attrs, body: inner }) => {
+    if (!attrs.source || !attrs.target) {
+      throw invalid('GraphML edge needs a source and a target');
+    }
+    const directed = attrs.directed ? attrs.directed === 'true' : directedGraph;
+    return {
+      nodes: [attrs.source, attrs.target],
+      sources: directed ? [attrs.source] : undefined,
+      attributes: dataOf(inner, keys),
+    };
+  });
+  for (const { body: inner } of xmlElements(body, 'hyperedge')) {
+    const endpoints = xmlElements(inner, 'endpoint').map(({ attrs }) => attrs);
+    const sources = endpoints.filter((e) => e.type === 'out').map((e) => e.node);
+    edges.push({
+      nodes: endpoints.map((e) => e.node),
+      sources: sources.length > 0 ? sources : undefined,
+      attributes: dataOf(inner, keys),
+    });
+  }
+
+  return build(nodes, edges, options);
+}
@@ -0,0 +1,88 @@
+import { ArkheError } from './errors.js';
+import { Hypergraph } from './hypergraph.js';
+
+export type InvariantKind =
+  | 'id-mismatch'
+  | 'duplicate-edge-id'
+  | 'missing-endpoint'
+  | 'degenerate-edge'
+  | 'invalid-direction'
+  | 'coherence-out-of-range'
+  | 'invalid-weight';
+
+export interface InvariantViolation {
+  kind: InvariantKind;
+  message: string;
+  nodeId?: string;
+  edgeId?: string;
+}
+
+/**
+ * Structural checks every hypergraph should pass: node IDs match their keys,
+ * edge IDs are unique, edges reference existing nodes, directed edges have
+ * both sources and targets, coherence lies in [0, 1] and weights are finite.
+ * Meant for tests and fuzzing of code that manipulates graphs directly.
+ */
+export function checkInvariants(h: Hypergraph): InvariantViolation[] {
+  const violations: InvariantViolation[] = [];
+
+  for (const [key, node] of h.nodes) {
+    if (node.id !== key) {
+      violations.push({ kind: 'id-mismatch', message: `Node stored under ${key} has id ${node.id}`, nodeId: key });
+    }
+    if (!(node.coherence >= 0 && node.coherence <= 1)) {
+      violations.push({
+        kind: 'coherence-out-of-range',
+        message: `Node ${key} has coherence ${node.coherence}`,
+        nodeId: key,
+      });
+    }
+  }
+
+  const edgeIds = new Set<string>();
+  for (const edge of h.edges) {
+    if (edgeIds.has(edge.id)) {
+      violations.push({ kind: 'duplicate-edge-id', message: `Edge ID ${edge.id} is used twice`, edgeId: edge.id });
+    }
+    edgeIds.add(edge.id);
+    for (const nid of edge.nodes) {
+      if (!h.nodes.has(nid)) {
+        violations.push({
+          kind: 'missing-endpoint',
+          message: `Edge ${edge.id} references missing node ${nid}`,
+          nodeId: nid,
+          edgeId: edge.id,
+        });
+      }
+    }
+    if (edge.nodes.size < 2) {
+      violations.push({ kind: 'degenerate-edge', message: `Edge ${edge.id} has ${edge.nodes.size} node(s)`, edgeId: edge.id });
+    }
+    if (edge.sources) {
+      const outside = Array.from(edge.sources).filter((nid) => !edge.nodes.has(nid));
+      if (edge.sources.size === 0 || edge.sources.size >= edge.nodes.size || outside.length > 0) {
+        violations.push({
+          kind: 'invalid-direction',
+          message: `Directed edge ${edge.id} needs sources and targets drawn from its nodes`,
+          edgeId: edge.id,
+        });
+      }
+    }
+    if (!Number.isFinite(edge.weight)) {
+      violations.push({ kind: 'invalid-weight', message: `Edge ${edge.id} has weight ${edge.weight}`, edgeId: edge.id });
+    }
+  }
+  return violations;
+}
+
+/** Throw an ArkheError INVARIANT_VIOLATION listing every failed check. */
+export function assertInvariants(h: Hypergraph): void {
+  const violations = checkInvariants(h);
+  if (violations.length > 0) {
+    throw new ArkheError(
+      'INVARIANT_VIOLATION',
+      `Hypergraph invariants violated: ${violations.map((v) => v.message).join('; ')}`,
+      violations[0].nodeId,
+    );
+  }
+}
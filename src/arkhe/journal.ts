@@ -0,0 +1,157 @@
+import { ArkheError } from './errors.js';
+import { Hypergraph } from './hypergraph.js';
+import type { ArkheNodeData, HypergraphState } from './types.js';
+
+/**
+ * One serializable step of graph history. Records carry explicit IDs and
+ * values, so folding the same stream always yields the same graph.
+ */
+export type GraphRecord =
+  | { type: 'NodeAdded'; id: string; data: ArkheNodeData; coherence?: number }
+  | { type: 'NodeRemoved'; id: string }
+  | { type: 'StateSet'; id: string; data: ArkheNodeData }
+  /** The value as given; entangled partners are not shifted, as their moves are records of their own. */
+  | { type: 'CoherenceSet'; id: string; coherence: number }
+  | { type: 'EdgeAdded'; id: string; nodes: string[]; sources?: string[]; weight: number; label?: string }
+  | { type: 'EdgeRemoved'; id: string }
+  | { type: 'Entangled'; a: string; b: string; strength: number }
+  | { type: 'Disentangled'; a: string; b: string };
+
+/** Apply one record to a graph; throws ArkheError if the record does not fit the graph. */
+export function applyRecord(h: Hypergraph, record: GraphRecord): void {
+  switch (record.type) {
+    case 'NodeAdded':
+      h.addNode(record.id, record.data);
+      if (record.coherence !== undefined) h.setCoherence(record.id, record.coherence, false);
+      break;
+    case 'NodeRemoved':
+      if (!h.removeNode(record.id)) throw ArkheError.nodeNotFound(record.id);
+      break;
+    case 'StateSet':
+      h.setNodeData(record.id, record.data);
+      break;
+    case 'CoherenceSet':
+      h.setCoherence(record.id, record.coherence, false);
+      break;
+    case 'EdgeAdded':
+      h.insertEdge(record.nodes, record.sources, record.weight, record.label, record.id);
+      break;
+    case 'EdgeRemoved':
+      if (!h.removeEdge(record.id)) {
+        throw new ArkheError('EDGE_NOT_FOUND', `Edge ${record.id} does not exist`);
+      }
+      break;
+    case 'Entangled':
+      h.entangle(record.a, record.b, record.strength);
+      break;
+    case 'Disentangled':
+      if (!h.disentangle(record.a, record.b)) {
+        throw new ArkheError('INVALID_ENTANGLEMENT', `Nodes ${record.a} and ${record.b} are not entangled`);
+      }
+      break;
+  }
+}
+
+/** Serialized journal: the compacted base state plus the records after it. */
+export interface JournalState {
+  base: HypergraphState;
+  baseOffset: number;
+  records: GraphRecord[];
+}
+
+export interface JournalOptions {
+  /** State the stream starts from. Defaults to an empty graph. */
+  base?: HypergraphState;
+}
+
+/**
+ * Event-sourced graph: `graph` is the fold of an append-only record stream
+ * over a base state. Offsets count records from the start of the stream and
+ * survive compaction, which folds a prefix into the base. Mutate `graph` only
+ * through append(), or the stream no longer describes it. Node data is kept
+ * by reference, as Hypergraph methods replace rather than mutate it.
+ */
+export class GraphJournal {
+  public readonly graph: Hypergraph;
+  private base: HypergraphState;
+  private baseOffset = 0;
+  private log: GraphRecord[] = [];
+
+  constructor(options: JournalOptions = {}) {
+    this.base = options.base ?? { nodes: {}, edges: [] };
+    this.graph = Hypergraph.fromJSON(this.base);
+  }
+
+  /** Offset just past the last record. */
+  public get offset(): number {
+    return this.baseOffset + this.log.length;
+  }
+
+  /** Earliest offset still available to rebuild(), records() and branch(). */
+  public get start(): number {
+    return this.baseOffset;
+  }
+
+  /**
+   * Apply records to `graph` and append them, in order. A record that does not
+   * apply throws and is not appended; the ones before it stay. Returns the new offset.
+   */
+  public append(...records: GraphRecord[]): number {
+    for (const record of records) {
+      applyRecord(this.graph, record);
+      this.log.push(record);
+    }
+    return this.offset;
+  }
+
+  /** Records in [from, to). */
+  public records(from: number = this.start, to: number = this.offset): GraphRecord[] {
+    this.checkOffset(from);
+    this.checkOffset(to);
+    return this.log.slice(from - this.baseOffset, to - this.baseOffset);
+  }
+
+  /** A fresh graph holding the fold of every record before `offset`. */
+  public rebuild(offset: number = this.offset): Hypergraph {
+    const h = Hypergraph.fromJSON(this.base);
+    for (const record of this.records(this.start, offset)) applyRecord(h, record);
+    return h;
+  }
+
+  /** Fold the records before `offset` into the base; earlier offsets can no longer be rebuilt. */
+  public compact(offset: number = this.offset): void {
+    const base = this.rebuild(offset).toJSON();
+    this.log = this.log.slice(offset - this.baseOffset);
+    this.base = base;
+    this.baseOffset = offset;
+  }
+
+  /** An independent journal sharing this one's history up to `offset`. */
+  public branch(offset: number = this.offset): GraphJournal {
+    const records = this.records(this.start, offset);
+    const branch = new GraphJournal({ base: this.base });
+    branch.baseOffset = this.baseOffset;
+    for (const record of records) branch.append(record);
+    return branch;
+  }
+
+  public toJSON(): JournalState {
+    return { base: this.base, baseOffset: this.baseOffset, records: this.log.slice() };
+  }
+
+  public static fromJSON(state: JournalState): GraphJournal {
+    const journal = new GraphJournal({ base: state.base });
+    journal.baseOffset = state.baseOffset;
+    for (const record of state.records) journal.append(record);
+    return journal;
+  }
+
+  private checkOffset(offset: number): void {
+    if (!Number.isInteger(offset) || offset < this.start || offset > this.offset) {
+      throw new ArkheError(
+        'INVALID_ARGUMENT',
+        `Offset ${offset} outside the journal's range [${this.start}, ${this.offset}]`,
+      );
+    }
+  }
+}
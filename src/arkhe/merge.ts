@@ -0,0 +1,119 @@
+import { Hypergraph } from './hypergraph.js';
+import type { ArkheHyperedge, ArkheNode } from './types.js';
+
+/** Decides the surviving data and coherence when both graphs hold the same ID. */
+export type MergeResolver = (existing: ArkheNode, incoming: ArkheNode) => Pick<ArkheNode, 'data' | 'coherence'>;
+
+export type MergePolicy =
+  | 'keep-highest-coherence'
+  | 'prefer-self'
+  | 'prefer-other'
+  | 'rename-with-suffix'
+  | MergeResolver;
+
+export interface MergeConflict {
+  nodeId: string;
+  resolution: 'kept-self' | 'took-other' | 'renamed' | 'custom';
+  renamedTo?: string;
+}
+
+/** An incoming edge whose ID was taken by a different edge in the target. */
+export interface EdgeConflict {
+  edgeId: string;
+  renamedTo: string;
+}
+
+export interface MergeReport {
+  nodesAdded: number;
+  edgesAdded: number;
+  entanglementsAdded: number;
+  conflicts: MergeConflict[];
+  edgeConflicts: EdgeConflict[];
+}
+
+function sameSet(a: Set<string> | undefined, b: Set<string> | undefined): boolean {
+  if (!a || !b) return a === b;
+  return a.size === b.size && Array.from(a).every((x) => b.has(x));
+}
+
+function sameEdge(a: ArkheHyperedge, b: ArkheHyperedge): boolean {
+  return sameSet(a.nodes, b.nodes) && sameSet(a.sources, b.sources) && a.weight === b.weight && a.label === b.label;
+}
+
+function freeId(target: Hypergraph, other: Hypergraph, id: string): string {
+  for (let n = 2; ; n++) {
+    const candidate = `${id}#${n}`;
+    if (!target.nodes.has(candidate) && !other.nodes.has(candidate)) return candidate;
+  }
+}
+
+/**
+ * Merge `other` into `target` in place. ID collisions are settled by the
+ * policy, which sets only the colliding node's coherence (entangled partners
+ * do not shift). Edges and entanglements from `other` follow any renames. An
+ * edge whose ID already names an identical edge in `target` (e.g. both graphs
+ * came from one snapshot) is skipped; a different edge under that ID is
+ * added with a fresh one and reported. Links already present keep their
+ * strength unless the policy is 'prefer-other'.
+ */
+export function merge(target: Hypergraph, other: Hypergraph, policy: MergePolicy = 'prefer-self'): MergeReport {
+  const report: MergeReport = { nodesAdded: 0, edgesAdded: 0, entanglementsAdded: 0, conflicts: [], edgeConflicts: [] };
+  const renames = new Map<string, string>();
+
+  for (const incoming of other.nodes.values()) {
+    const existing = target.nodes.get(incoming.id);
+    if (!existing) {
+      target.addNode(incoming.id, structuredClone(incoming.data));
+      target.setCoherence(incoming.id, incoming.coherence, false);
+      report.nodesAdded++;
+      continue;
+    }
+
+    if (policy === 'rename-with-suffix') {
+      const renamed = freeId(target, other, incoming.id);
+      renames.set(incoming.id, renamed);
+      target.addNode(renamed, structuredClone(incoming.data));
+      target.setCoherence(renamed, incoming.coherence, false);
+      report.nodesAdded++;
+      report.conflicts.push({ nodeId: incoming.id, resolution: 'renamed', renamedTo: renamed });
+      continue;
+    }
+
+    if (typeof policy === 'function') {
+      const resolved = policy(existing, incoming);
+      target.setNodeData(existing.id, resolved.data);
+      target.setCoherence(existing.id, resolved.coherence, false);
+      report.conflicts.push({ nodeId: incoming.id, resolution: 'custom' });
+      continue;
+    }
+
+    const takeOther = policy === 'keep-highest-coherence'
+      ? incoming.coherence > existing.coherence
+      : policy === 'prefer-other';
+    if (takeOther) {
+      target.setNodeData(existing.id, structuredClone(incoming.data));
+      target.setCoherence(existing.id, incoming.coherence, false);
+    }
+    report.conflicts.push({ nodeId: incoming.id, resolution: takeOther ? 'took-other' : 'kept-self' });
+  }
+
+  const existingEdges = new Map(target.edges.map((e) => [e.id, e]));
+  const rename = (nid: string) => renames.get(nid) ?? nid;
+  for (const edge of other.edges) {
+    const sources = edge.sources && new Set(Array.from(edge.sources, rename));
+    const renamed: ArkheHyperedge = { ...edge, nodes: new Set(Array.from(edge.nodes, rename)), sources };
+    const existing = existingEdges.get(edge.id);
+    if (existing && sameEdge(existing, renamed)) continue;
+    const added = target.insertEdge(renamed.nodes, sources, edge.weight, edge.label, existing ? undefined : edge.id);
+    if (existing) report.edgeConflicts.push({ edgeId: edge.id, renamedTo: added.id });
+    report.edgesAdded++;
+  }
+
+  for (const { a, b, strength } of other.entanglements()) {
+    const link = target.entanglementsOf(rename(a)).find((l) => l.b === rename(b));
+    if (link && (link.strength === strength || policy !== 'prefer-other')) continue;
+    target.entangle(rename(a), rename(b), strength);
+    if (!link) report.entanglementsAdded++;
+  }
+  return report;
+}
@@ -0,0 +1,94 @@
+import { Hypergraph } from './hypergraph.js';
+import type { ArkheNode } from './types.js';
+
+/** Maps a node to the category it is counted under for entropy. */
+export type Discretizer = (node: ArkheNode) => string;
+
+export const byType: Discretizer = (node) => String(node.data.type ?? 'untyped');
+
+export interface ConnectivityStats {
+  nodeCount: number;
+  edgeCount: number;
+  directedEdges: number;
+  meanDegree: number;
+  meanWeightedDegree: number;
+  maxDegree: number;
+  isolatedNodes: number;
+  meanEdgeSize: number;
+  meanEdgeWeight: number;
+}
+
+export interface MetricsReport {
+  globalCoherence: number;
+  entropy: number;
+  connectivity: ConnectivityStats;
+}
+
+export function globalCoherence(h: Hypergraph): number {
+  return h.totalCoherence();
+}
+
+/** Shannon entropy (bits) of the node distribution over discretized categories. */
+export function stateEntropy(h: Hypergraph, discretize: Discretizer = byType): number {
+  if (h.nodes.size === 0) return 0;
+  const counts = new Map<string, number>();
+  for (const node of h.nodes.values()) {
+    const key = discretize(node);
+    counts.set(key, (counts.get(key) ?? 0) + 1);
+  }
+  let entropy = 0;
+  for (const count of counts.values()) {
+    const p = count / h.nodes.size;
+    entropy -= p * Math.log2(p);
+  }
+  return entropy;
+}
+
+export function connectivityStats(h: Hypergraph): ConnectivityStats {
+  const degrees = new Map<string, number>();
+  for (const id of h.nodes.keys()) degrees.set(id, 0);
+
+  let sizeSum = 0;
+  let weightSum = 0;
+  let weightedDegreeSum = 0;
+  let directedEdges = 0;
+  for (const edge of h.edges) {
+    sizeSum += edge.nodes.size;
+    weightSum += edge.weight;
+    weightedDegreeSum += edge.weight * edge.nodes.size;
+    if (edge.sources) directedEdges++;
+    for (const nid of edge.nodes) {
+      degrees.set(nid, (degrees.get(nid) ?? 0) + 1);
+    }
+  }
+
+  let degreeSum = 0;
+  let maxDegree = 0;
+  let isolatedNodes = 0;
+  for (const d of degrees.values()) {
+    degreeSum += d;
+    maxDegree = Math.max(maxDegree, d);
+    if (d === 0) isolatedNodes++;
+  }
+
+  return {
+    nodeCount: h.nodes.size,
+    edgeCount: h.edges.length,
+    directedEdges,
+    meanDegree: h.nodes.size > 0 ? degreeSum / h.nodes.size : 0,
+    meanWeightedDegree: h.nodes.size > 0 ? weightedDegreeSum / h.nodes.size : 0,
+    maxDegree,
+    isolatedNodes,
+    meanEdgeSize: h.edges.length > 0 ? sizeSum / h.edges.length : 0,
+    meanEdgeWeight: h.edges.length > 0 ? weightSum / h.edges.length : 0,
+  };
+}
+
+/** Snapshot of the headline metrics, e.g. for logging after each bootstrap step. */
+export function collectMetrics(h: Hypergraph, discretize: Discretizer = byType): MetricsReport {
+  return {
+    globalCoherence: globalCoherence(h),
+    entropy: stateEntropy(h, discretize),
+    connectivity: connectivityStats(h),
+  };
+}
@@ -0,0 +1,174 @@
+import { ArkheError } from './errors.js';
+import { Hypergraph } from './hypergraph.js';
+import type { ArkheHyperedge, ArkheNode } from './types.js';
+
+export interface MotifNode {
+  name: string;
+  /** State predicate a matching node must satisfy. */
+  where?: (node: ArkheNode) => boolean;
+}
+
+/**
+ * A small structural pattern: named node variables and the hyperedges that
+ * must join them. Each pattern edge matches a hyperedge with exactly those
+ * members; direction is ignored.
+ */
+export interface MotifPattern {
+  nodes: MotifNode[];
+  edges: string[][];
+}
+
+/** Pattern variable name to matched node ID. */
+export type MotifMatch = Record<string, string>;
+
+export interface MotifOptions {
+  /** Stop after this many matches. */
+  limit?: number;
+}
+
+export interface IsomorphismOptions {
+  /** Extra condition on paired nodes, e.g. equal data.type. */
+  nodeMatch?: (a: ArkheNode, b: ArkheNode) => boolean;
+  /** Backtracking budget before giving up with SEARCH_LIMIT_EXCEEDED. Defaults to 1e6. */
+  maxSteps?: number;
+}
+
+function memberKey(ids: Iterable<string>): string {
+  return JSON.stringify(Array.from(ids).sort());
+}
+
+function directedKey(edge: ArkheHyperedge, rename: (id: string) => string = (id) => id): string {
+  return JSON.stringify([
+    Array.from(edge.nodes, rename).sort(),
+    edge.sources ? Array.from(edge.sources, rename).sort() : null,
+  ]);
+}
+
+/**
+ * Every injective assignment of pattern variables to nodes that satisfies the
+ * pattern. Symmetric patterns are reported once per automorphism.
+ */
+export function findMotifs(h: Hypergraph, pattern: MotifPattern, options: MotifOptions = {}): MotifMatch[] {
+  const names = pattern.nodes.map((n) => n.name);
+  for (const edge of pattern.edges) {
+    for (const name of edge) {
+      if (!names.includes(name)) {
+        throw new ArkheError('INVALID_EDGE', `Motif edge references unknown variable ${name}`);
+      }
+    }
+  }
+  const present = new Set(h.edges.map((e) => memberKey(e.nodes)));
+  const limit = options.limit ?? Infinity;
+  const matches: MotifMatch[] = [];
+  const assignment = new Map<string, string>();
+  const used = new Set<string>();
+
+  // Pattern edges become checkable once their last variable is assigned.
+  const order = names.map((name) => ({
+    node: pattern.nodes.find((n) => n.name === name)!,
+    edgesEndingHere: pattern.edges.filter((e) =>
+      e.includes(name) && e.every((v) => names.indexOf(v) <= names.indexOf(name))),
+    earlierNeighbor: pattern.edges
+      .filter((e) => e.includes(name))
+      .flat()
+      .find((v) => names.indexOf(v) < names.indexOf(name)),
+  }));
+
+  const visit = (depth: number) => {
+    if (matches.length >= limit) return;
+    if (depth === order.length) {
+      matches.push(Object.fromEntries(assignment));
+      return;
+    }
+    const { node: variable, edgesEndingHere, earlierNeighbor } = order[depth];
+    const candidates = earlierNeighbor !== undefined
+      ? h.neighbors(assignment.get(earlierNeighbor)!)
+      : h.nodes.keys();
+    for (const id of candidates) {
+      if (used.has(id)) continue;
+      if (variable.where && !variable.where(h.nodes.get(id)!)) continue;
+      assignment.set(variable.name, id);
+      const fits = edgesEndingHere.every((e) => present.has(memberKey(e.map((v) => assignment.get(v)!))));
+      if (fits) {
+        used.add(id);
+        visit(depth + 1);
+        used.delete(id);
+      }
+      assignment.delete(variable.name);
+    }
+  };
+  visit(0);
+  return matches;
+}
+
+/**
+ * A node bijection from `a` to `b` that maps the hyperedge multiset of `a`
+ * (members and direction) exactly onto that of `b`, or undefined if none exists.
+ */
+export function findIsomorphism(
+  a: Hypergraph,
+  b: Hypergraph,
+  options: IsomorphismOptions = {},
+): Map<string, string> | undefined {
+  if (a.nodes.size !== b.nodes.size || a.edges.length !== b.edges.length) return undefined;
+  const maxSteps = options.maxSteps ?? 1e6;
+
+  const target = new Map<string, number>();
+  for (const edge of b.edges) {
+    const key = directedKey(edge);
+    target.set(key, (target.get(key) ?? 0) + 1);
+  }
+  const present = new Set(b.edges.map((e) => memberKey(e.nodes)));
+
+  // Most constrained nodes first.
+  const order = Array.from(a.nodes.keys()).sort((x, y) => a.degree(y) - a.degree(x));
+  const position = new Map(order.map((id, i) => [id, i]));
+  const closing = new Map<string, ArkheHyperedge[]>();
+  for (const edge of a.edges) {
+    // Memberless edges close at no node; the final multiset comparison still counts them.
+    if (edge.nodes.size === 0) continue;
+    const last = Array.from(edge.nodes).reduce((p, q) => (position.get(q)! > position.get(p)! ? q : p));
+    if (!closing.has(last)) closing.set(last, []);
+    closing.get(last)!.push(edge);
+  }
+
+  const mapping = new Map<string, string>();
+  const used = new Set<string>();
+  let steps = 0;
+
+  function visit(depth: number): boolean {
+    if (depth === order.length) {
+      const counts = new Map<string, number>();
+      for (const edge of a.edges) {
+        const key = directedKey(edge, (id) => mapping.get(id)!);
+        counts.set(key, (counts.get(key) ?? 0) + 1);
+      }
+      return Array.from(counts).every(([key, n]) => target.get(key) === n);
+    }
+    const id = order[depth];
+    const node = a.nodes.get(id)!;
+    for (const candidate of b.nodes.values()) {
+      if (used.has(candidate.id)) continue;
+      if (++steps > maxSteps) {
+        throw new ArkheError('SEARCH_LIMIT_EXCEEDED', `Isomorphism search exceeded ${maxSteps} steps`);
+      }
+      if (b.degree(candidate.id) !== a.degree(id)) continue;
+      if (options.nodeMatch && !options.nodeMatch(node, candidate)) continue;
+      mapping.set(id, candidate.id);
+      const fits = (closing.get(id) ?? []).every((e) =>
+        present.has(memberKey(Array.from(e.nodes, (nid) => mapping.get(nid)!))));
+      if (fits) {
+        used.add(candidate.id);
+        if (visit(depth + 1)) return true;
+        used.delete(candidate.id);
+      }
+      mapping.delete(id);
+    }
+    return false;
+  }
+  return visit(0) ? mapping : undefined;
+}
+
+export function isIsomorphic(a: Hypergraph, b: Hypergraph, options: IsomorphismOptions = {}): boolean {
+  return findIsomorphism(a, b, options) !== undefined;
+}
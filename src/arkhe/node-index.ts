@@ -0,0 +1,128 @@
+import { Hypergraph } from './hypergraph.js';
+import type { HypergraphEvent } from './events.js';
+import type { ArkheNode } from './types.js';
+
+const UNTYPED = '';
+
+/**
+ * Secondary indices over a hypergraph: nodes by `data.type` and a
+ * coherence-ordered list. Kept current through the graph's event bus, so
+ * mutations must go through Hypergraph methods (setCoherence, updateNode, ...);
+ * call rebuild() after writing node fields directly.
+ */
+export class NodeIndex {
+  private h: Hypergraph;
+  private byType: Map<string, Set<string>> = new Map();
+  private ordered: ArkheNode[] = []; // descending coherence
+  // Coherence each ordered entry was placed with. Bulk writers such as
+  // propagateCoherence update every node before announcing any, so live
+  // values cannot serve as the search key while their events are pending.
+  private keys: number[] = [];
+  private unsubscribe: () => void;
+
+  constructor(h: Hypergraph) {
+    this.h = h;
+    this.rebuild();
+    this.unsubscribe = h.events.subscribe((e) => this.apply(e));
+  }
+
+  public rebuild(): void {
+    this.byType.clear();
+    this.ordered = [];
+    this.keys = [];
+    for (const node of this.h.nodes.values()) {
+      this.insert(node);
+    }
+  }
+
+  /** Stop tracking the graph. */
+  public detach(): void {
+    this.unsubscribe();
+  }
+
+  public nodesOfType(type: string | undefined): ArkheNode[] {
+    const ids = this.byType.get(type ?? UNTYPED);
+    if (!ids) return [];
+    return Array.from(ids, (id) => this.h.nodes.get(id)!);
+  }
+
+  public topKByCoherence(k: number): ArkheNode[] {
+    return this.ordered.slice(0, k);
+  }
+
+  public bottomKByCoherence(k: number): ArkheNode[] {
+    return this.ordered.slice(Math.max(0, this.ordered.length - k)).reverse();
+  }
+
+  private apply(event: HypergraphEvent): void {
+    switch (event.type) {
+      case 'NodeAdded':
+        this.insert(event.node);
+        break;
+      case 'NodeRemoved':
+        this.remove(event.node);
+        break;
+      case 'StateChanged': {
+        const node = this.h.nodes.get(event.nodeId);
+        if (!node) break;
+        this.typeSet(event.previous.type).delete(node.id);
+        this.typeSet(node.data.type).add(node.id);
+        break;
+      }
+      case 'CoherenceUpdated': {
+        const node = this.h.nodes.get(event.nodeId);
+        if (!node) break;
+        this.detachOrdered(node);
+        this.insertOrdered(node);
+        break;
+      }
+      case 'GraphRestored':
+      case 'BatchApplied':
+        this.rebuild();
+        break;
+      default:
+        break;
+    }
+  }
+
+  private typeSet(type: string | undefined): Set<string> {
+    const key = type ?? UNTYPED;
+    let set = this.byType.get(key);
+    if (!set) {
+      set = new Set();
+      this.byType.set(key, set);
+    }
+    return set;
+  }
+
+  private insert(node: ArkheNode): void {
+    this.typeSet(node.data.type).add(node.id);
+    this.insertOrdered(node);
+  }
+
+  private remove(node: ArkheNode): void {
+    this.typeSet(node.data.type).delete(node.id);
+    this.detachOrdered(node);
+  }
+
+  private detachOrdered(node: ArkheNode): void {
+    const i = this.ordered.findIndex((n) => n.id === node.id);
+    if (i >= 0) {
+      this.ordered.splice(i, 1);
+      this.keys.splice(i, 1);
+    }
+  }
+
+  private insertOrdered(node: ArkheNode): void {
+    // Binary search for the first position with lower coherence.
+    let lo = 0;
+    let hi = this.ordered.length;
+    while (lo < hi) {
+      const mid = (lo + hi) >> 1;
+      if (this.keys[mid] >= node.coherence) lo = mid + 1;
+      else hi = mid;
+    }
+    this.ordered.splice(lo, 0, node);
+    this.keys.splice(lo, 0, node.coherence);
+  }
+}
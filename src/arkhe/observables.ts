@@ -0,0 +1,171 @@
+import { Hypergraph } from './hypergraph.js';
+import { byType, stateEntropy } from './metrics.js';
+import type { Discretizer } from './metrics.js';
+
+/** A named scalar measured over the whole graph. */
+export interface Observable {
+  name: string;
+  measure(h: Hypergraph): number;
+}
+
+export const meanCoherence: Observable = {
+  name: 'mean_coherence',
+  measure: (h) => h.totalCoherence(),
+};
+
+/** Node count, optionally restricted to one `data.type`. */
+export function nodeCount(type?: string): Observable {
+  return {
+    name: type === undefined ? 'node_count' : `node_count:${type}`,
+    measure: (h) => {
+      if (type === undefined) return h.nodes.size;
+      let count = 0;
+      for (const node of h.nodes.values()) {
+        if (node.data.type === type) count++;
+      }
+      return count;
+    },
+  };
+}
+
+/** Hyperedges per possible node pair; 1 for a complete simple graph. */
+export const edgeDensity: Observable = {
+  name: 'edge_density',
+  measure: (h) => {
+    const n = h.nodes.size;
+    return n > 1 ? h.edges.length / ((n * (n - 1)) / 2) : 0;
+  },
+};
+
+export function entropyOf(discretize: Discretizer = byType): Observable {
+  return { name: 'entropy', measure: (h) => stateEntropy(h, discretize) };
+}
+
+export interface ObservationRow {
+  tick: number;
+  values: number[];
+}
+
+function csvField(value: string): string {
+  return /[",\n]/.test(value) ? `"${value.replace(/"/g, '""')}"` : value;
+}
+
+/** Samples a fixed set of observables every `every` ticks into a time series. */
+export class ObservableRecorder {
+  private observables: Observable[];
+  private every: number;
+  private ticks = 0;
+  public rows: ObservationRow[] = [];
+
+  constructor(observables: Observable[], every: number = 1) {
+    this.observables = observables;
+    this.every = Math.max(1, every);
+  }
+
+  /** Advance one tick, sampling when it falls on the interval (ticks 0, K, 2K, ...). */
+  public tick(h: Hypergraph): void {
+    if (this.ticks % this.every === 0) this.sample(h);
+    this.ticks++;
+  }
+
+  /** Record a row for the current tick regardless of the interval. */
+  public sample(h: Hypergraph): ObservationRow {
+    const row = { tick: this.ticks, values: this.observables.map((o) => o.measure(h)) };
+    this.rows.push(row);
+    return row;
+  }
+
+  /** (tick, value) pairs for one observable. */
+  public series(name: string): Array<[number, number]> {
+    const i = this.observables.findIndex((o) => o.name === name);
+    if (i < 0) return [];
+    return this.rows.map((row) => [row.tick, row.values[i]]);
+  }
+
+  public toCSV(): string {
+    const header = ['tick', ...this.observables.map((o) => o.name)].map(csvField).join(',');
+    return [header, ...this.rows.map((row) => [row.tick, ...row.values].join(','))].join('\n');
+  }
+}
+
+export interface NodeSample {
+  tick: number;
+  nodeId: string;
+  coherence: number;
+  /** Numeric leaves of the node data, keyed by dotted path (booleans as 0/1). */
+  fields: Record<string, number>;
+}
+
+export interface TickRange {
+  /** Inclusive. */
+  fromTick?: number;
+  /** Exclusive. */
+  toTick?: number;
+}
+
+function flattenNumeric(value: unknown, path: string, out: Record<string, number>): void {
+  if (typeof value === 'number') out[path] = value;
+  else if (typeof value === 'boolean') out[path] = value ? 1 : 0;
+  else if (value !== null && typeof value === 'object') {
+    for (const [key, child] of Object.entries(value)) {
+      flattenNumeric(child, path ? `${path}.${key}` : key, out);
+    }
+  }
+}
+
+/**
+ * Samples every node's coherence and numeric state fields every `every`
+ * ticks, for export as long-format CSV (one row per node per sampled tick).
+ * Non-numeric fields are skipped.
+ */
+export class NodeSeriesRecorder {
+  private every: number;
+  private ticks = 0;
+  public samples: NodeSample[] = [];
+
+  constructor(every: number = 1) {
+    this.every = Math.max(1, every);
+  }
+
+  public tick(h: Hypergraph): void {
+    if (this.ticks % this.every === 0) this.sample(h);
+    this.ticks++;
+  }
+
+  public sample(h: Hypergraph): void {
+    for (const node of h.nodes.values()) {
+      const fields: Record<string, number> = {};
+      flattenNumeric(node.data, '', fields);
+      this.samples.push({ tick: this.ticks, nodeId: node.id, coherence: node.coherence, fields });
+    }
+  }
+
+  /** CSV of the samples within the range; field columns are the union seen there, sorted. */
+  public toCSV(range: TickRange = {}): string {
+    const from = range.fromTick ?? -Infinity;
+    const to = range.toTick ?? Infinity;
+    const rows = this.samples.filter((s) => s.tick >= from && s.tick < to);
+    const columns = Array.from(new Set(rows.flatMap((s) => Object.keys(s.fields)))).sort();
+    const header = ['tick', 'node', 'coherence', ...columns].map(csvField).join(',');
+    return [
+      header,
+      ...rows.map((s) => [
+        s.tick,
+        csvField(s.nodeId),
+        s.coherence,
+        ...columns.map((c) => (c in s.fields ? s.fields[c] : '')),
+      ].join(',')),
+    ].join('\n');
+  }
+
+  /** One CSV per consecutive block of `ticksPerPart` ticks, skipping empty blocks. */
+  public partitionedCSV(ticksPerPart: number): Array<{ fromTick: number; toTick: number; csv: string }> {
+    const size = Math.max(1, ticksPerPart);
+    const starts = new Set(this.samples.map((s) => Math.floor(s.tick / size) * size));
+    return Array.from(starts).sort((a, b) => a - b).map((fromTick) => ({
+      fromTick,
+      toTick: fromTick + size,
+      csv: this.toCSV({ fromTick, toTick: fromTick + size }),
+    }));
+  }
+}
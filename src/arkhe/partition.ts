@@ -0,0 +1,106 @@
+import { ArkheError } from './errors.js';
+import { Hypergraph } from './hypergraph.js';
+import type { ArkheHyperedge } from './types.js';
+
+export interface PartitionOptions {
+  /** Allowed overshoot of the ideal part size, as a fraction. Defaults to 0.05. */
+  imbalance?: number;
+  /** Refinement sweeps after the greedy pass. Defaults to 4. */
+  refinePasses?: number;
+}
+
+export interface Partition {
+  /** Part index per node ID. */
+  assignment: Map<string, number>;
+  parts: string[][];
+  /** Hyperedges whose members span more than one part. */
+  cutEdges: ArkheHyperedge[];
+  /** Nodes incident to a cut hyperedge; these need synchronizing between parts. */
+  boundary: Set<string>;
+  /** Total weight of the cut hyperedges. */
+  cutWeight: number;
+}
+
+function breadthFirstOrder(h: Hypergraph): string[] {
+  const order: string[] = [];
+  const seen = new Set<string>();
+  for (const start of h.nodes.keys()) {
+    if (seen.has(start)) continue;
+    seen.add(start);
+    const queue = [start];
+    while (queue.length > 0) {
+      const id = queue.shift()!;
+      order.push(id);
+      for (const next of h.neighbors(id)) {
+        if (!seen.has(next)) {
+          seen.add(next);
+          queue.push(next);
+        }
+      }
+    }
+  }
+  return order;
+}
+
+/**
+ * Split the graph into k parts of near-equal size while keeping heavy
+ * hyperedges inside one part. Nodes are placed greedily in breadth-first
+ * order next to the neighbours they share the most edge weight with, then
+ * single-node moves that reduce the cut are applied while sizes allow.
+ */
+export function partition(h: Hypergraph, k: number, options: PartitionOptions = {}): Partition {
+  if (!Number.isInteger(k) || k < 1) {
+    throw new ArkheError('INVALID_ARGUMENT', `Cannot split a graph into ${k} parts`);
+  }
+  const capacity = Math.max(1, Math.ceil((h.nodes.size / k) * (1 + (options.imbalance ?? 0.05))));
+  const assignment = new Map<string, number>();
+  const sizes = new Array<number>(k).fill(0);
+
+  // Edge weight shared with each part, from the node's already-placed neighbours.
+  const affinity = (id: string) => {
+    const scores = new Array<number>(k).fill(0);
+    for (const edge of h.edgesOf(id)) {
+      for (const nid of edge.nodes) {
+        const part = assignment.get(nid);
+        if (nid !== id && part !== undefined) scores[part] += edge.weight;
+      }
+    }
+    return scores;
+  };
+  const bestPart = (scores: number[], allowed: (part: number) => boolean) => {
+    let best = -1;
+    for (let p = 0; p < k; p++) {
+      if (!allowed(p)) continue;
+      if (best < 0 || scores[p] > scores[best] || (scores[p] === scores[best] && sizes[p] < sizes[best])) best = p;
+    }
+    return best;
+  };
+
+  for (const id of breadthFirstOrder(h)) {
+    const part = bestPart(affinity(id), (p) => sizes[p] < capacity);
+    assignment.set(id, part);
+    sizes[part]++;
+  }
+
+  for (let pass = 0; pass < (options.refinePasses ?? 4); pass++) {
+    let moved = false;
+    for (const [id, current] of assignment) {
+      const scores = affinity(id);
+      const target = bestPart(scores, (p) => p === current || sizes[p] < capacity);
+      if (target !== current && scores[target] > scores[current]) {
+        assignment.set(id, target);
+        sizes[current]--;
+        sizes[target]++;
+        moved = true;
+      }
+    }
+    if (!moved) break;
+  }
+
+  const parts: string[][] = Array.from({ length: k }, () => []);
+  for (const [id, part] of assignment) parts[part].push(id);
+  const cutEdges = h.edges.filter((e) => new Set(Array.from(e.nodes, (nid) => assignment.get(nid))).size > 1);
+  const boundary = new Set(cutEdges.flatMap((e) => Array.from(e.nodes)));
+  const cutWeight = cutEdges.reduce((sum, e) => sum + e.weight, 0);
+  return { assignment, parts, cutEdges, boundary, cutWeight };
+}
@@ -0,0 +1,84 @@
+import type { Hypergraph } from './hypergraph.js';
+import type { NodeIndex } from './node-index.js';
+import type { ArkheNode } from './types.js';
+
+/**
+ * Chainable node selector. Type and adjacency constraints seed the candidate
+ * set (from a NodeIndex when one is given) before the remaining filters run,
+ * so selective queries avoid scanning every node.
+ */
+export class NodeQuery {
+  private h: Hypergraph;
+  private index?: NodeIndex;
+  private type?: string;
+  private typeSet = false;
+  private adjacentTo: string[] = [];
+  private filters: Array<(node: ArkheNode) => boolean> = [];
+
+  constructor(h: Hypergraph, index?: NodeIndex) {
+    this.h = h;
+    this.index = index;
+  }
+
+  public ofType(type: string | undefined): this {
+    this.type = type;
+    this.typeSet = true;
+    return this;
+  }
+
+  public coherenceGt(threshold: number): this {
+    return this.where((n) => n.coherence > threshold);
+  }
+
+  public coherenceLt(threshold: number): this {
+    return this.where((n) => n.coherence < threshold);
+  }
+
+  public coherenceBetween(min: number, max: number): this {
+    return this.where((n) => n.coherence >= min && n.coherence <= max);
+  }
+
+  /** Keep nodes sharing a hyperedge with `nodeId`. */
+  public connectedTo(nodeId: string): this {
+    this.adjacentTo.push(nodeId);
+    return this;
+  }
+
+  public where(predicate: (node: ArkheNode) => boolean): this {
+    this.filters.push(predicate);
+    return this;
+  }
+
+  public collect(): ArkheNode[] {
+    let candidates: Iterable<ArkheNode>;
+    if (this.adjacentTo.length > 0) {
+      candidates = Array.from(this.h.neighbors(this.adjacentTo[0]), (id) => this.h.nodes.get(id)!);
+    } else if (this.typeSet && this.index) {
+      candidates = this.index.nodesOfType(this.type);
+    } else {
+      candidates = this.h.nodes.values();
+    }
+
+    const neighborSets = this.adjacentTo.slice(1).map((id) => this.h.neighbors(id));
+    const result: ArkheNode[] = [];
+    for (const node of candidates) {
+      if (this.typeSet && node.data.type !== this.type) continue;
+      if (!neighborSets.every((set) => set.has(node.id))) continue;
+      if (!this.filters.every((f) => f(node))) continue;
+      result.push(node);
+    }
+    return result;
+  }
+
+  public ids(): string[] {
+    return this.collect().map((n) => n.id);
+  }
+
+  public count(): number {
+    return this.collect().length;
+  }
+
+  public first(): ArkheNode | undefined {
+    return this.collect()[0];
+  }
+}
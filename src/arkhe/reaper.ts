@@ -0,0 +1,73 @@
+import { Hypergraph } from './hypergraph.js';
+import type { ArkheHyperedge, ArkheNode } from './types.js';
+
+export interface ReaperOptions {
+  /** Nodes with coherence strictly below this are decaying. */
+  threshold: number;
+  /** Consecutive decaying observations before a node is removed. Defaults to 1. */
+  ticks?: number;
+  /** Called with each reaped node and copies of its hyperedges before removal. */
+  archive?: (node: ArkheNode, edges: ArkheHyperedge[]) => void;
+}
+
+/**
+ * Removes nodes whose coherence stays below a threshold. Call observe() once
+ * per tick of whatever loop drives the graph; reaped nodes leave through
+ * removeNode (so their hyperedges are pruned as usual) and are announced
+ * together in one Reaped event.
+ */
+export class CoherenceReaper {
+  private h: Hypergraph;
+  private threshold: number;
+  private ticks: number;
+  private archive?: (node: ArkheNode, edges: ArkheHyperedge[]) => void;
+  private lowStreak: Map<string, number> = new Map();
+
+  constructor(h: Hypergraph, options: ReaperOptions) {
+    this.h = h;
+    this.threshold = options.threshold;
+    this.ticks = Math.max(1, options.ticks ?? 1);
+    this.archive = options.archive;
+  }
+
+  /** Run one pass and return the nodes it removed. */
+  public observe(): ArkheNode[] {
+    const doomed: string[] = [];
+    for (const node of this.h.nodes.values()) {
+      if (node.coherence >= this.threshold) {
+        this.lowStreak.delete(node.id);
+        continue;
+      }
+      const streak = (this.lowStreak.get(node.id) ?? 0) + 1;
+      this.lowStreak.set(node.id, streak);
+      if (streak >= this.ticks) doomed.push(node.id);
+    }
+    for (const id of this.lowStreak.keys()) {
+      if (!this.h.nodes.has(id)) this.lowStreak.delete(id);
+    }
+
+    const reaped: ArkheNode[] = [];
+    for (const id of doomed) {
+      if (this.archive) {
+        const edges = this.h.edgesOf(id).map((e) => ({
+          ...e,
+          nodes: new Set(e.nodes),
+          sources: e.sources && new Set(e.sources),
+        }));
+        this.archive(this.h.nodes.get(id)!, edges);
+      }
+      const node = this.h.removeNode(id);
+      if (node) reaped.push(node);
+      this.lowStreak.delete(id);
+    }
+    if (reaped.length > 0) {
+      this.h.events.emit({ type: 'Reaped', nodes: reaped });
+    }
+    return reaped;
+  }
+
+  /** Consecutive low observations recorded for a node so far. */
+  public streak(nodeId: string): number {
+    return this.lowStreak.get(nodeId) ?? 0;
+  }
+}
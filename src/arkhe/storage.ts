@@ -0,0 +1,238 @@
+import { mkdirSync, readdirSync, readFileSync, renameSync, rmSync, writeFileSync } from 'fs';
+import { join } from 'path';
+import { isDeepStrictEqual } from 'util';
+import { loadEnvelope, saveEnvelope } from './envelope.js';
+import { ArkheError } from './errors.js';
+import { Hypergraph } from './hypergraph.js';
+import { applyRecord } from './journal.js';
+import type { HypergraphEnvelope, LoadOptions } from './envelope.js';
+import type { GraphRecord } from './journal.js';
+import type { HypergraphState } from './types.js';
+
+/** Key-value store for serialized checkpoints. */
+export interface StorageBackend {
+  get(key: string): string | undefined;
+  put(key: string, value: string): void;
+  delete(key: string): void;
+  keys(): string[];
+}
+
+export class MemoryBackend implements StorageBackend {
+  private entries: Map<string, string> = new Map();
+
+  public get(key: string): string | undefined {
+    return this.entries.get(key);
+  }
+
+  public put(key: string, value: string): void {
+    this.entries.set(key, value);
+  }
+
+  public delete(key: string): void {
+    this.entries.delete(key);
+  }
+
+  public keys(): string[] {
+    return Array.from(this.entries.keys());
+  }
+}
+
+/** One `<key>.json` file per entry in `dir`, written to a temporary file and renamed into place. */
+export class FileBackend implements StorageBackend {
+  private dir: string;
+
+  constructor(dir: string) {
+    this.dir = dir;
+    mkdirSync(dir, { recursive: true });
+  }
+
+  public get(key: string): string | undefined {
+    try {
+      return readFileSync(this.path(key), 'utf8');
+    } catch {
+      return undefined;
+    }
+  }
+
+  public put(key: string, value: string): void {
+    const tmp = `${this.path(key)}.tmp`;
+    writeFileSync(tmp, value);
+    renameSync(tmp, this.path(key));
+  }
+
+  public delete(key: string): void {
+    rmSync(this.path(key), { force: true });
+  }
+
+  public keys(): string[] {
+    return readdirSync(this.dir).filter((f) => f.endsWith('.json')).map((f) => f.slice(0, -'.json'.length));
+  }
+
+  private path(key: string): string {
+    return join(this.dir, `${key}.json`);
+  }
+}
+
+interface FullCheckpoint {
+  kind: 'full';
+  tick: number;
+  envelope: HypergraphEnvelope;
+}
+
+interface DeltaCheckpoint {
+  kind: 'delta';
+  tick: number;
+  parent: number;
+  records: GraphRecord[];
+}
+
+type Checkpoint = FullCheckpoint | DeltaCheckpoint;
+
+export interface CheckpointOptions {
+  /** Defaults to a new MemoryBackend. */
+  backend?: StorageBackend;
+  /** Write a full checkpoint after this many deltas. Defaults to 10. */
+  fullEvery?: number;
+  /** Passed to loadEnvelope for full checkpoints. */
+  load?: LoadOptions;
+}
+
+const PREFIX = 'checkpoint-';
+
+/**
+ * Records that turn `before` into `after`, or undefined when replaying them
+ * would not reproduce `after`'s node and edge order. Changed edges are replaced.
+ */
+function stateDelta(before: HypergraphState, after: HypergraphState): GraphRecord[] | undefined {
+  const records: GraphRecord[] = [];
+  const nextEdges = new Map(after.edges.map((e) => [e.id, e]));
+  const kept: string[] = [];
+  for (const edge of before.edges) {
+    if (edge.id !== undefined && isDeepStrictEqual(nextEdges.get(edge.id), edge)) kept.push(edge.id);
+    else if (edge.id !== undefined) records.push({ type: 'EdgeRemoved', id: edge.id });
+    else return undefined;
+  }
+  const keptIds = new Set(kept);
+  const order = [...kept, ...after.edges.filter((e) => !keptIds.has(e.id)).map((e) => e.id)];
+  if (!isDeepStrictEqual(order, after.edges.map((e) => e.id))) return undefined;
+  const staying = Object.keys(before.nodes).filter((id) => id in after.nodes);
+  const arriving = Object.keys(after.nodes).filter((id) => !(id in before.nodes));
+  if (!isDeepStrictEqual([...staying, ...arriving], Object.keys(after.nodes))) return undefined;
+
+  for (const id of Object.keys(before.nodes)) {
+    if (!(id in after.nodes)) records.push({ type: 'NodeRemoved', id });
+  }
+  for (const [id, data] of Object.entries(after.nodes)) {
+    const coherence = after.coherence?.[id];
+    if (!(id in before.nodes)) {
+      records.push({ type: 'NodeAdded', id, data, coherence });
+      continue;
+    }
+    if (!isDeepStrictEqual(before.nodes[id], data)) records.push({ type: 'StateSet', id, data });
+    if (coherence !== undefined && before.coherence?.[id] !== coherence) {
+      records.push({ type: 'CoherenceSet', id, coherence });
+    }
+  }
+  for (const edge of after.edges) {
+    if (!keptIds.has(edge.id)) records.push({ type: 'EdgeAdded', ...edge, id: edge.id! });
+  }
+
+  const key = (a: string, b: string) => `${a}\u0000${b}`;
+  const links = new Map((after.entanglements ?? []).map((l) => [key(l.a, l.b), l]));
+  for (const { a, b, strength } of before.entanglements ?? []) {
+    const next = links.get(key(a, b));
+    // NodeRemoved drops a node's links by itself.
+    if (!next && a in after.nodes && b in after.nodes) records.push({ type: 'Disentangled', a, b });
+    if (next && next.strength === strength) links.delete(key(a, b));
+  }
+  for (const { a, b, strength } of links.values()) records.push({ type: 'Entangled', a, b, strength });
+  return records;
+}
+
+/**
+ * Tick-keyed checkpoints built on saveEnvelope/loadEnvelope. Between full
+ * checkpoints, each save stores only the records that turn the previous
+ * checkpoint into the current graph, so loading replays a short chain. The
+ * store keeps the last saved state in memory to compute deltas; a store opened
+ * over earlier checkpoints starts its next chain with a full checkpoint.
+ */
+export class CheckpointStore {
+  private backend: StorageBackend;
+  private fullEvery: number;
+  private load: LoadOptions;
+  private last?: { tick: number; state: HypergraphState };
+  private sinceFull = 0;
+
+  constructor(options: CheckpointOptions = {}) {
+    this.backend = options.backend ?? new MemoryBackend();
+    this.fullEvery = Math.max(1, options.fullEvery ?? 10);
+    this.load = options.load ?? {};
+  }
+
+  /** Saved ticks, ascending. */
+  public ticks(): number[] {
+    return this.backend.keys()
+      .filter((k) => k.startsWith(PREFIX))
+      .map((k) => Number(k.slice(PREFIX.length)))
+      .sort((a, b) => a - b);
+  }
+
+  public latest(): number | undefined {
+    const ticks = this.ticks();
+    return ticks[ticks.length - 1];
+  }
+
+  /** Save the graph as of `tick`, which must be later than every saved tick. */
+  public saveCheckpoint(tick: number, h: Hypergraph): void {
+    const latest = this.latest();
+    if (!Number.isInteger(tick) || (latest !== undefined && tick <= latest)) {
+      throw new ArkheError('INVALID_ARGUMENT', `Checkpoint tick ${tick} must be an integer after ${latest}`);
+    }
+    const state = h.toJSON();
+    const records = this.last !== undefined && this.last.tick === latest && this.sinceFull < this.fullEvery
+      ? stateDelta(this.last.state, state)
+      : undefined;
+    let checkpoint: Checkpoint;
+    if (records) {
+      checkpoint = { kind: 'delta', tick, parent: this.last!.tick, records };
+      this.sinceFull++;
+    } else {
+      checkpoint = { kind: 'full', tick, envelope: saveEnvelope(h) };
+      this.sinceFull = 0;
+    }
+    this.backend.put(PREFIX + tick, JSON.stringify(checkpoint));
+    // Copied, so data edited in place after the save still shows up in the next delta.
+    this.last = { tick, state: structuredClone(state) };
+  }
+
+  /** Rebuild the graph saved at `tick`; throws CHECKPOINT_NOT_FOUND if there is none. */
+  public loadCheckpoint(tick: number): Hypergraph {
+    const chain: DeltaCheckpoint[] = [];
+    let checkpoint = this.read(tick);
+    while (checkpoint.kind === 'delta') {
+      chain.push(checkpoint);
+      checkpoint = this.read(checkpoint.parent);
+    }
+    const h = loadEnvelope(checkpoint.envelope, this.load);
+    for (let i = chain.length - 1; i >= 0; i--) {
+      for (const record of chain[i].records) applyRecord(h, record);
+    }
+    return h;
+  }
+
+  /** Delete checkpoints after `tick`, e.g. before resuming a run from it. */
+  public discardAfter(tick: number): void {
+    for (const t of this.ticks()) {
+      if (t > tick) this.backend.delete(PREFIX + t);
+    }
+    if (this.last && this.last.tick > tick) this.last = undefined;
+  }
+
+  private read(tick: number): Checkpoint {
+    const raw = this.backend.get(PREFIX + tick);
+    if (raw === undefined) {
+      throw new ArkheError('CHECKPOINT_NOT_FOUND', `Checkpoint ${tick} does not exist`);
+    }
+    return JSON.parse(raw) as Checkpoint;
+  }
+}
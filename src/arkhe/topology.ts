@@ -0,0 +1,126 @@
+import { ArkheError } from './errors.js';
+import { Hypergraph } from './hypergraph.js';
+import type { ArkheNode } from './types.js';
+
+/** Distance between two nodes; must be non-negative and symmetric. */
+export type Metric = (a: ArkheNode, b: ArkheNode) => number;
+
+/** Euclidean distance over one or more numeric data fields. */
+export function fieldMetric(...keys: string[]): Metric {
+  return (a, b) => {
+    let sum = 0;
+    for (const key of keys) {
+      const d = Number(a.data[key]) - Number(b.data[key]);
+      sum += d * d;
+    }
+    return Math.sqrt(sum);
+  };
+}
+
+function centerNode(h: Hypergraph, centerId: string): ArkheNode {
+  const center = h.nodes.get(centerId);
+  if (!center) {
+    throw ArkheError.nodeNotFound(centerId);
+  }
+  return center;
+}
+
+/** Nodes within `radius` of the center (excluding the center), nearest first. */
+export function nodesWithin(h: Hypergraph, centerId: string, radius: number, metric: Metric): ArkheNode[] {
+  const center = centerNode(h, centerId);
+  return Array.from(h.nodes.values())
+    .filter((n) => n.id !== centerId)
+    .map((n) => ({ node: n, distance: metric(center, n) }))
+    .filter((entry) => entry.distance <= radius)
+    .sort((x, y) => x.distance - y.distance)
+    .map((entry) => entry.node);
+}
+
+/** The k nodes closest to the center (excluding the center). */
+export function nearestNeighbors(h: Hypergraph, centerId: string, k: number, metric: Metric): ArkheNode[] {
+  const center = centerNode(h, centerId);
+  return Array.from(h.nodes.values())
+    .filter((n) => n.id !== centerId)
+    .map((n) => ({ node: n, distance: metric(center, n) }))
+    .sort((x, y) => x.distance - y.distance)
+    .slice(0, k)
+    .map((entry) => entry.node);
+}
+
+export interface ComponentLabels {
+  /** Component index per node ID. */
+  labels: Map<string, number>;
+  /** Node IDs per component, largest first. */
+  components: string[][];
+}
+
+/** Weakly connected components: hyperedge direction is ignored. */
+export function connectedComponents(h: Hypergraph): ComponentLabels {
+  const adjacency = new Map<string, Set<string>>();
+  for (const id of h.nodes.keys()) adjacency.set(id, new Set());
+  for (const edge of h.edges) {
+    for (const a of edge.nodes) {
+      for (const b of edge.nodes) {
+        if (a !== b) adjacency.get(a)?.add(b);
+      }
+    }
+  }
+
+  const seen = new Set<string>();
+  const groups: string[][] = [];
+  for (const start of h.nodes.keys()) {
+    if (seen.has(start)) continue;
+    const group: string[] = [];
+    const queue = [start];
+    seen.add(start);
+    while (queue.length > 0) {
+      const id = queue.shift()!;
+      group.push(id);
+      for (const next of adjacency.get(id) ?? []) {
+        if (!seen.has(next)) {
+          seen.add(next);
+          queue.push(next);
+        }
+      }
+    }
+    groups.push(group);
+  }
+
+  groups.sort((x, y) => y.length - x.length);
+  const labels = new Map<string, number>();
+  groups.forEach((group, i) => {
+    for (const id of group) labels.set(id, i);
+  });
+  return { labels, components: groups };
+}
+
+/**
+ * Nodes reachable from `nodeId` (including itself). Undirected hyperedges
+ * connect all members; directed ones are only crossed from sources to targets.
+ */
+export function reachableFrom(h: Hypergraph, nodeId: string): Set<string> {
+  if (!h.nodes.has(nodeId)) {
+    throw ArkheError.nodeNotFound(nodeId);
+  }
+  const reached = new Set<string>([nodeId]);
+  const queue = [nodeId];
+  while (queue.length > 0) {
+    const id = queue.shift()!;
+    for (const edge of h.edgesOf(id)) {
+      if (edge.sources && !edge.sources.has(id)) continue;
+      for (const next of edge.nodes) {
+        if (edge.sources?.has(next)) continue;
+        if (!reached.has(next)) {
+          reached.add(next);
+          queue.push(next);
+        }
+      }
+    }
+  }
+  return reached;
+}
+
+/** True when the graph has at most one weakly connected component. */
+export function isConnected(h: Hypergraph): boolean {
+  return connectedComponents(h).components.length <= 1;
+}
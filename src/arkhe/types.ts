@@ -14,11 +14,55 @@ export interface ArkheHyperedge {
   id: string;
   nodes: Set<string>;
   weight: number;
+  label?: string;
+  sources?: Set<string>; // set for directed edges: sources -> the remaining nodes
+}
+
+/** Correlated coherence between two nodes; strength is a coefficient in [-1, 1]. */
+export interface ArkheEntanglement {
+  a: string;
+  b: string;
+  strength: number;
 }
 
 export interface HypergraphState {
   nodes: Record<string, ArkheNodeData>;
-  edges: Array<{ nodes: string[]; weight: number }>;
+  edges: Array<{ id?: string; nodes: string[]; weight: number; label?: string; sources?: string[] }>;
+  coherence?: Record<string, number>; // per-node coherence, omitted by older dumps
+  entanglements?: ArkheEntanglement[];
+}
+
+/** One step of Hypergraph.applyBatch. */
+export type GraphMutation =
+  | { op: 'addNode'; id?: string; data?: ArkheNodeData }
+  | { op: 'removeNode'; id: string }
+  | { op: 'setState'; id: string; data: ArkheNodeData }
+  | { op: 'addEdge'; nodes: string[]; weight?: number; label?: string }
+  | { op: 'removeEdge'; edgeId: string }
+  | { op: 'adjustCoherence'; id: string; delta: number };
+
+/** Weighted influence of one neighbor on a node during propagation. */
+export interface CoherenceCoupling {
+  node: ArkheNode;
+  weight: number;
+}
+
+/** Computes a node's next coherence from its current value and its couplings. */
+export type CoherenceKernel = (node: ArkheNode, couplings: CoherenceCoupling[]) => number;
+
+export interface PropagationOptions {
+  maxIterations?: number;
+  tolerance?: number;
+  kernel?: CoherenceKernel;
+  /** Recompute only around nodes touched since the last pass. Defaults to false. */
+  incremental?: boolean;
+}
+
+export interface PropagationReport {
+  iterations: number;
+  converged: boolean;
+  globalCoherence: number;
+  deltas: Record<string, number>; // final minus initial coherence per recomputed node
 }
 
 export interface CognitiveState {